@@ -35,7 +35,13 @@ pub fn get_whitelisted_token_price(mint_address: Pubkey, amount: u64, decimals:
     SOLANA_WHITELISTED_TOKENS
         .iter()
         .find(|(mint, _)| *mint == mint_address)
-        .map(|(_, price)| *price * amount_with_decimal as f64)
+        .map(|(_, price)| *price * amount_with_decimal)
+}
+
+// dollar value of a SOL amount, as counted against the instant bridge cap
+pub fn get_sol_dollar_value(lamports: u64) -> f64 {
+    lamports as f64 / solana_program::native_token::LAMPORTS_PER_SOL as f64
+        * SOLANA_DOLLAR_VALUE as f64
 }
 
 // Helper function to check if token is whitelisted
@@ -48,3 +54,11 @@ pub fn is_token_whitelisted(mint_address: Pubkey) -> bool {
 pub const SOLANA_DOLLAR_VALUE: u64 = 200;
 pub const SOLANA_DOLLAR_CAP_PER_EPOCH: u64 = 1_000_000;
 pub const SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS: u64 = 6 * 60 * 60; // 6 hours
+
+pub const MAX_FEE_DISCOUNT_BPS: u16 = 10_000; // 100%
+
+pub const DELIST_GRACE_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60; // 7 days
+pub const MAX_MAPPING_MIGRATIONS: usize = 8;
+pub const DELIVERY_BITMAP_PAGE_BYTES: usize = 32; // 256 sequences per page
+pub const SOURCE_PROOF_FINALITY_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60; // 7 days
+pub const REFUND_CLOCK_SKEW_SECONDS: u64 = 60 * 60; // 1 hour
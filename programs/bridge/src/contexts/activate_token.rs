@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::states::{TokenInfo, TokenStatus};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct ActivateToken<'info> {
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl ActivateToken<'_> {
+    pub fn activate_token(&mut self) -> Result<()> {
+        require!(
+            self.token_info.status == TokenStatus::Proposed,
+            BridgeHandlerError::InvalidTokenStatus
+        );
+
+        msg!("activating token {:?}", self.mint.key());
+        self.token_info.status = TokenStatus::Active;
+        Ok(())
+    }
+}
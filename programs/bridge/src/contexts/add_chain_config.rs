@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{ChainConfig, ChainKind, GuardianInfo};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+#[instruction(chain_id: u16)]
+pub struct AddChainConfig<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + ChainConfig::INIT_SPACE,
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_id.to_be_bytes().as_ref()],
+        bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        seeds = [b"guardian_info", bridge_handler.key().as_ref()],
+        bump = guardian_info.bump
+    )]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    system_program: Program<'info, System>,
+}
+
+impl AddChainConfig<'_> {
+    pub fn add_chain_config(
+        &mut self,
+        bump: u8,
+        chain_id: u16,
+        chain_kind: ChainKind,
+        emitter: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            chain_id != 0 && chain_id != self.bridge_handler.chain.chain_id(),
+            BridgeHandlerError::InvalidChain
+        );
+        chain_kind.check_universal_address(&emitter)?;
+
+        msg!("adding chain config for chain id {}", chain_id);
+
+        self.chain_config.bump = bump;
+        self.chain_config.chain_id = chain_id;
+        self.chain_config.chain_kind = chain_kind;
+        self.chain_config.pause = false;
+        self.chain_config.emitter = emitter;
+        self.chain_config.guardian_info = self.guardian_info.key();
+        self.chain_config.fee_info = self.bridge_handler.fee_info;
+        // update_instant_bridge_cap sets the default cap, update_chain_config changes it per chain
+        self.chain_config.instant_bridge_cap_per_epoch =
+            self.bridge_handler.instant_bridge_cap_remained_dollar;
+        self.chain_config.instant_bridge_cap_remained_dollar =
+            self.bridge_handler.instant_bridge_cap_remained_dollar;
+        self.chain_config.instant_bridge_cap_epoch = Clock::get()?.epoch;
+        self.chain_config.created_at = Clock::get()?.unix_timestamp as u64;
+        Ok(())
+    }
+}
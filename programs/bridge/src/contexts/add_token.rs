@@ -8,7 +8,7 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-use crate::states::{Chain, TokenInfo};
+use crate::states::{Chain, TokenInfo, TokenStatus};
 use crate::{errors::BridgeHandlerError, states::BridgeHandler};
 
 #[derive(Accounts)]
@@ -63,6 +63,7 @@ impl AddToken<'_> {
         &mut self,
         token_info_bump: u8,
         decimal: u8,
+        source_decimal: u8,
         name: Option<String>,
         symbol: Option<String>,
         uri: Option<String>,
@@ -87,23 +88,34 @@ impl AddToken<'_> {
                 (true, false)
             };
 
-            msg!("Creating token info with decimal: {}, solana_mint: {:?}, solayer_mint: {:?},  is_solana_native_token: {:?}, is_solayer_native_token: {:?}",
-                decimal, solana_mint, solayer_mint, is_solana_native_token, is_solayer_native_token
+            let (solana_decimals, solayer_decimals) = if chain == Chain::Solana {
+                (decimal, source_decimal)
+            } else {
+                (source_decimal, decimal)
+            };
+
+            msg!("Creating token info with decimal: {}, source_decimal: {}, solana_mint: {:?}, solayer_mint: {:?},  is_solana_native_token: {:?}, is_solayer_native_token: {:?}",
+                decimal, source_decimal, solana_mint, solayer_mint, is_solana_native_token, is_solayer_native_token
             );
 
-            self.token_info.bump = token_info_bump;
-            self.token_info.solana_mint = solana_mint;
-            self.token_info.solayer_mint = solayer_mint;
-            self.token_info.is_solana_native_token = is_solana_native_token;
-            self.token_info.is_solayer_native_token = is_solayer_native_token;
-            self.token_info.pause = false;
+            self.token_info.init(
+                token_info_bump,
+                solana_mint,
+                solayer_mint,
+                is_solana_native_token,
+                is_solayer_native_token,
+                solana_decimals,
+                solayer_decimals,
+                TokenStatus::Active,
+            );
 
             // only create metadata on solana
-            if name.is_some() && symbol.is_some() && uri.is_some() && chain == Chain::Solana {
+            if let (Some(name), Some(symbol), Some(uri), Chain::Solana) = (name, symbol, uri, chain)
+            {
                 let token_metadata = DataV2 {
-                    name: name.unwrap(),
-                    symbol: symbol.unwrap(),
-                    uri: uri.unwrap(),
+                    name,
+                    symbol,
+                    uri,
                     seller_fee_basis_points: 0,
                     creators: None,
                     collection: None,
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::metadata::mpl_token_metadata::{
+    MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::constants::METADATA_CREATION_FEE;
+use crate::states::{Chain, TokenAttestation, TokenInfo, TokenStatus};
+use crate::utils::{check_mint_extensions, read_token_metadata};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct AttestToken<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        has_one = fee_vault,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Metaplex metadata of the mint, address is checked when it is used
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenAttestation::INIT_SPACE,
+        seeds = [b"token_attestation", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    token_attestation: Box<Account<'info, TokenAttestation>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + TokenInfo::INIT_SPACE,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    #[account(mut)]
+    /// CHECK: no check needed other than address check
+    fee_vault: AccountInfo<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl AttestToken<'_> {
+    pub fn attest_token(&mut self, bumps: AttestTokenBumps) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require_keys_neq!(
+            self.mint.key(),
+            native_mint::ID,
+            BridgeHandlerError::WsolNotSupported
+        );
+        if self.bridge_handler.strict_mint_extensions {
+            check_mint_extensions(&self.mint.to_account_info())?;
+        }
+
+        let chain = self.bridge_handler.chain;
+        let mint = self.mint.key();
+
+        if !self.token_info.is_solana_native_token && !self.token_info.is_solayer_native_token {
+            // the wrapped mint metadata is created on the other chain
+            if chain == Chain::Solayer {
+                self.transfer_sol_to_fee_vault(METADATA_CREATION_FEE)?;
+            }
+
+            let wrapped_mint = TokenInfo::wrapped_mint(&self.bridge_handler.key(), &mint);
+
+            let (solana_mint, solayer_mint) = if chain == Chain::Solana {
+                (mint, wrapped_mint)
+            } else {
+                (wrapped_mint, mint)
+            };
+            // register_token creates the wrapped mint with the attested decimals, and as
+            // attestations are permissionless the token admin still has to activate the token
+            self.token_info.init(
+                bumps.token_info,
+                solana_mint,
+                solayer_mint,
+                chain == Chain::Solana,
+                chain == Chain::Solayer,
+                self.mint.decimals,
+                self.mint.decimals,
+                TokenStatus::Proposed,
+            );
+        } else {
+            require!(
+                (chain == Chain::Solana && self.token_info.is_solana_native_token)
+                    || (chain == Chain::Solayer && self.token_info.is_solayer_native_token),
+                BridgeHandlerError::WrappedTokenAttestation
+            );
+        }
+
+        let (name, symbol, uri) =
+            read_token_metadata(&self.mint.to_account_info(), &self.metadata)?;
+        require!(
+            name.len() <= MAX_NAME_LENGTH
+                && symbol.len() <= MAX_SYMBOL_LENGTH
+                && uri.len() <= MAX_URI_LENGTH,
+            BridgeHandlerError::InvalidTokenMetadata
+        );
+
+        let msg_hash = TokenAttestation::message_hash(
+            chain.chain_id(),
+            &self.bridge_handler.key().to_bytes(),
+            &mint,
+            self.mint.decimals,
+            &name,
+            &symbol,
+            &uri,
+        );
+
+        msg!(
+            "attesting {:?} with decimals: {}, name: {}, symbol: {}, uri: {}",
+            mint,
+            self.mint.decimals,
+            name,
+            symbol,
+            uri
+        );
+
+        self.token_attestation.bump = bumps.token_attestation;
+        self.token_attestation.mint = mint;
+        self.token_attestation.decimals = self.mint.decimals;
+        self.token_attestation.name = name;
+        self.token_attestation.symbol = symbol;
+        self.token_attestation.uri = uri;
+        self.token_attestation.msg_hash = msg_hash;
+        self.token_attestation.created_at = Clock::get()?.unix_timestamp as u64;
+
+        Ok(())
+    }
+
+    fn transfer_sol_to_fee_vault(&mut self, lamports: u64) -> Result<()> {
+        let ctx = CpiContext::new(
+            self.system_program.to_account_info(),
+            Transfer {
+                from: self.payer.to_account_info(),
+                to: self.fee_vault.to_account_info(),
+            },
+        );
+
+        transfer(ctx, lamports)
+    }
+}
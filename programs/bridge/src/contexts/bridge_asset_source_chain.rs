@@ -1,12 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{burn, transfer_checked, Burn, TransferChecked};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{burn, Burn};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
-use solana_program::hash::hash;
+use solana_program::sysvar::instructions::ID as IX_ID;
 
-use crate::constants::{MAX_ADDITIONAL_SOL_GAS, METADATA_CREATION_FEE, MIN_SOL_BRIDGE_AMOUNT};
-use crate::states::BridgeProofSourceChain;
+use crate::constants::{MAX_ADDITIONAL_SOL_GAS, MIN_SOL_BRIDGE_AMOUNT};
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, FeeExemption, SenderSequence,
+};
+use crate::utils::{apply_fee_exemption, check_mint_extensions, transfer_checked_with_hook};
 use crate::{
     errors::BridgeHandlerError,
     states::{BridgeHandler, Chain, TokenInfo},
@@ -20,7 +24,6 @@ extern "C" {
 }
 
 #[derive(Accounts)]
-#[instruction(bridge_proof_nonce: u64)]
 pub struct BridgeAssetSourceChain<'info> {
     #[account(mut)]
     signer: Signer<'info>,
@@ -37,57 +40,103 @@ pub struct BridgeAssetSourceChain<'info> {
     )]
     signer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        mut,
         has_one = fee_vault,
         seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
         bump = bridge_handler.bump
     )]
     bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
     #[account(mut)]
     /// CHECK: Instead of checking in macro, we do manual check to reduce stack size
     bridge_handler_vault: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SenderSequence::INIT_SPACE,
+        seeds = [b"sender_sequence", bridge_handler.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    sender_sequence: Box<Account<'info, SenderSequence>>,
     #[account(
         init,
         payer = signer,
         space = 8 + BridgeProofSourceChain::INIT_SPACE,
-        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), signer.key().as_ref(), bridge_proof_nonce.to_be_bytes().as_ref()],
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sender_sequence.next_sequence.to_be_bytes().as_ref()],
         bump
     )]
     bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
     #[account(
-        init_if_needed,
-        payer = signer,
-        space = 8 + TokenInfo::INIT_SPACE,
         seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
         bump
     )]
-    token_info: Box<Account<'info, TokenInfo>>,
+    /// CHECK: loaded manually so that unlisted mints are rejected with TokenNotExists
+    token_info: UncheckedAccount<'info>,
     #[account(mut)]
     /// CHECK: no check needed other than address check
     fee_vault: AccountInfo<'info>,
+    // additional gas is held here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
     token_program: Interface<'info, TokenInterface>,
     associated_token_program: Program<'info, AssociatedToken>,
     system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"fee_exemption", bridge_handler.key().as_ref(), fee_exemption.exempt_account.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+    /// CHECK: only address check is needed
+    #[account(address = IX_ID)]
+    ix_sysvar: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> BridgeAssetSourceChain<'info> {
     pub fn bridge_asset_source_chain(
         &mut self,
-        token_info_bump: u8,
+        bumps: &BridgeAssetSourceChainBumps,
         amount: u64,
-        recipient: Pubkey,
-        target_mint: Pubkey,
+        recipient: [u8; 32],
         additional_sol_gas: u64,
-    ) -> Result<u64> {
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64)> {
         self.init_if_needed_and_check_bridge_handler_vault()?;
 
         require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        // wSOL is unwrapped into the native SOL path so SOL has a single representation
+        require_keys_neq!(
+            self.mint.key(),
+            native_mint::ID,
+            BridgeHandlerError::WsolNotSupported
+        );
+        let token_info = self.load_token_info()?;
+        token_info.check_bridgeable()?;
+        TokenInfo::check_counterpart_chain(self.bridge_handler.chain, self.chain_config.chain_id)?;
+        if self.bridge_handler.strict_mint_extensions {
+            check_mint_extensions(&self.mint.to_account_info())?;
+        }
         require!(
             self.signer_vault.amount >= amount,
             BridgeHandlerError::InsufficientAmount,
         );
 
-        self.transfer_sol_to_fee_vault(self.bridge_handler.fee_info.bridge_asset_fee)?;
+        let bridge_asset_fee = apply_fee_exemption(
+            self.chain_config.fee_info.bridge_asset_fee,
+            &self.signer.key(),
+            self.fee_exemption
+                .as_deref()
+                .map(|fee_exemption| &**fee_exemption),
+            self.ix_sysvar.as_deref(),
+        )?;
+        self.transfer_sol_to_fee_vault(bridge_asset_fee)?;
 
         require!(
             additional_sol_gas <= MAX_ADDITIONAL_SOL_GAS,
@@ -104,77 +153,84 @@ impl<'info> BridgeAssetSourceChain<'info> {
             if self.bridge_handler.chain == Chain::Solayer {
                 self.burn_sol(additional_sol_gas)?;
             } else {
-                self.transfer_sol_to_bridge_handler(additional_sol_gas)?;
+                self.transfer_sol_to_sol_vault(additional_sol_gas)?;
             }
         }
 
         let chain = self.bridge_handler.chain;
 
-        if !self.token_info.is_solana_native_token && !self.token_info.is_solayer_native_token {
-            // token info not exists before, then it is a native token on current chain
-            // token info not exists, then it is a native token on current chain
-            self.transfer_token(amount, self.bridge_handler_vault.to_account_info())?;
-
-            if chain == Chain::Solayer {
-                self.transfer_sol_to_fee_vault(METADATA_CREATION_FEE)?;
-            }
-
-            self.token_info.bump = token_info_bump;
+        // dust below the message precision is never taken from the sender
+        let (_, dust) = token_info.normalize_amount(chain, amount)?;
+        let transfer_amount = amount
+            .checked_sub(dust)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        require!(transfer_amount > 0, BridgeHandlerError::InsufficientAmount);
+        if dust > 0 {
+            msg!("returning dust: {:?}", dust);
+        }
 
-            if chain == Chain::Solana {
-                self.token_info.solana_mint = self.mint.key();
-                self.token_info.solayer_mint = target_mint;
-                self.token_info.is_solana_native_token = true;
-                self.token_info.is_solayer_native_token = false;
-            } else {
-                self.token_info.solana_mint = target_mint;
-                self.token_info.solayer_mint = self.mint.key();
-                self.token_info.is_solana_native_token = false;
-                self.token_info.is_solayer_native_token = true;
-            }
-            self.token_info.pause = false;
+        let bridged_amount = if (chain == Chain::Solana && token_info.is_solana_native_token)
+            || (chain == Chain::Solayer && token_info.is_solayer_native_token)
+        {
+            // any dust left over by transfer fees stays in the vault
+            self.lock_token(transfer_amount, remaining_accounts)?
         } else {
-            // token info already exists
-            require!(!self.token_info.pause, BridgeHandlerError::TokenPaused);
-            if (chain == Chain::Solana && self.token_info.is_solana_native_token)
-                || (chain == Chain::Solayer && self.token_info.is_solayer_native_token)
-            {
-                self.transfer_token(amount, self.bridge_handler_vault.to_account_info())?;
-            } else if (chain == Chain::Solana && !self.token_info.is_solana_native_token)
-                || (chain == Chain::Solayer && !self.token_info.is_solayer_native_token)
-            {
-                self.burn_token(amount)?;
-            }
-        }
+            self.burn_token(transfer_amount)?;
+            transfer_amount
+        };
+        let (message_amount, _) = token_info.normalize_amount(chain, bridged_amount)?;
+        token_info.check_transfer_amount(message_amount)?;
 
         msg!(
             "bridging {:?} token of {:?} to {:?}",
-            amount,
+            bridged_amount,
             self.mint.key(),
             recipient
         );
+        msg!("message amount: {:?}", message_amount);
 
-        let nonce = self.bridge_handler.nonce;
+        let nonce = self
+            .sender_sequence
+            .allocate(bumps.sender_sequence, self.signer.key())?;
         msg!("nonce: {:?}", nonce);
-        self.bridge_handler.nonce = nonce.checked_add(1).unwrap();
-        Ok(nonce)
+        Ok((nonce, message_amount))
     }
 
     pub fn issue_bridge_proof(
         &mut self,
         bridge_proof_bump: u8,
-        bridge_proof_nonce: u64,
+        sequence: u64,
         amount: u64,
-        recipient: Pubkey,
+        recipient: [u8; 32],
+        additional_sol_gas: u64,
+        deadline: u64,
     ) -> Result<()> {
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
         self.bridge_proof.bump = bridge_proof_bump;
-        let mut message_data = Vec::new();
-        message_data.extend_from_slice(&self.signer.key().to_bytes());
-        message_data.extend_from_slice(&recipient.to_bytes());
-        message_data.extend_from_slice(&self.mint.key().to_bytes());
-        message_data.extend_from_slice(&amount.to_be_bytes());
-        message_data.extend_from_slice(&bridge_proof_nonce.to_be_bytes());
-        self.bridge_proof.msg_hash = hash(message_data.as_ref()).to_bytes();
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&recipient)?;
+        let token_info = self.load_token_info()?;
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence,
+            deadline,
+            payload: BridgePayload::Token {
+                source_mint: self.mint.key(),
+                target_mint: token_info.counterpart_mint(self.bridge_handler.chain),
+                amount,
+                additional_sol_gas,
+            },
+        };
+        self.bridge_proof.msg_hash = message.hash(self.chain_config.chain_kind)?;
         self.bridge_proof.user_account = self.signer.key();
         self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
         Ok(())
@@ -193,18 +249,69 @@ impl<'info> BridgeAssetSourceChain<'info> {
         burn(ctx, amount)
     }
 
-    fn transfer_token(&mut self, amount: u64, target_vault: AccountInfo<'info>) -> Result<()> {
-        let ctx = CpiContext::new(
-            self.token_program.to_account_info(),
-            TransferChecked {
-                from: self.signer_vault.to_account_info(),
-                to: target_vault,
-                mint: self.mint.to_account_info(),
-                authority: self.signer.to_account_info(),
-            },
-        );
+    // Token-2022 mints with the TransferFee extension deliver less than `amount` to the
+    // vault, so the vault delta is what the bridge is collateralized with
+    fn lock_token(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<u64> {
+        let vault_amount_before = self.bridge_handler_vault_amount()?;
+        self.transfer_token(
+            amount,
+            self.bridge_handler_vault.to_account_info(),
+            remaining_accounts,
+        )?;
+        let vault_amount_after = self.bridge_handler_vault_amount()?;
+
+        let received_amount = vault_amount_after
+            .checked_sub(vault_amount_before)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        require!(received_amount > 0, BridgeHandlerError::InsufficientAmount);
+
+        if received_amount != amount {
+            msg!(
+                "transfer fee withheld: {:?}",
+                amount.saturating_sub(received_amount)
+            );
+        }
+        Ok(received_amount)
+    }
+
+    fn load_token_info(&self) -> Result<TokenInfo> {
+        if self.token_info.owner != &crate::ID {
+            return Err(BridgeHandlerError::TokenNotExists.into());
+        }
 
-        transfer_checked(ctx, amount, self.mint.decimals)
+        let data = self.token_info.try_borrow_data()?;
+        TokenInfo::try_deserialize(&mut data.as_ref())
+            .map_err(|e| e.with_account_name("token_info"))
+    }
+
+    fn bridge_handler_vault_amount(&self) -> Result<u64> {
+        let data = self.bridge_handler_vault.try_borrow_data()?;
+        let token_account = TokenAccount::try_deserialize(&mut data.as_ref())
+            .map_err(|e| e.with_account_name("bridge_handler_vault"))?;
+        Ok(token_account.amount)
+    }
+
+    fn transfer_token(
+        &mut self,
+        amount: u64,
+        target_vault: AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        transfer_checked_with_hook(
+            &self.token_program.to_account_info(),
+            self.signer_vault.to_account_info(),
+            self.mint.to_account_info(),
+            target_vault,
+            self.signer.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            &[],
+        )
     }
 
     fn transfer_sol_to_fee_vault(&mut self, lamports: u64) -> Result<()> {
@@ -219,12 +326,12 @@ impl<'info> BridgeAssetSourceChain<'info> {
         transfer(ctx, lamports)
     }
 
-    fn transfer_sol_to_bridge_handler(&mut self, lamports: u64) -> Result<()> {
+    fn transfer_sol_to_sol_vault(&mut self, lamports: u64) -> Result<()> {
         let ctx = CpiContext::new(
             self.system_program.to_account_info(),
             Transfer {
                 from: self.signer.to_account_info(),
-                to: self.bridge_handler.to_account_info(),
+                to: self.sol_vault.to_account_info(),
             },
         );
 
@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
-use solana_program::hash::hash;
+use anchor_lang::system_program::{allocate, assign, transfer, Allocate, Assign, Transfer};
+use anchor_spl::token::{
+    close_account, initialize_account3, spl_token::native_mint, transfer_checked, CloseAccount,
+    InitializeAccount3, Mint, Token, TokenAccount, TransferChecked,
+};
+use solana_program::sysvar::instructions::ID as IX_ID;
 
-use crate::constants::{MIN_SOL_BRIDGE_AMOUNT, SOL_MINT_PUBKEY};
-use crate::states::{BridgeProofSourceChain, Chain};
+use crate::constants::MIN_SOL_BRIDGE_AMOUNT;
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, Chain, ChainConfig, FeeExemption,
+    SenderSequence,
+};
+use crate::utils::apply_fee_exemption;
 use crate::{errors::BridgeHandlerError, states::BridgeHandler};
 
 // The below precompile is used to burn SOL on Solayer only
@@ -14,40 +22,102 @@ extern "C" {
 }
 
 #[derive(Accounts)]
-#[instruction(bridge_proof_nonce: u64)]
 pub struct BridgeAssetSourceChainSol<'info> {
     #[account(mut)]
     signer: Signer<'info>,
     #[account(
-        mut,
         has_one = fee_vault,
         seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
         bump = bridge_handler.bump
     )]
     bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SenderSequence::INIT_SPACE,
+        seeds = [b"sender_sequence", bridge_handler.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    sender_sequence: Box<Account<'info, SenderSequence>>,
     #[account(
         init,
         payer = signer,
         space = 8 + BridgeProofSourceChain::INIT_SPACE,
-        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), signer.key().as_ref(), bridge_proof_nonce.to_be_bytes().as_ref()],
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sender_sequence.next_sequence.to_be_bytes().as_ref()],
         bump
     )]
     bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
     #[account(mut)]
     /// CHECK: no check needed other than address check
     fee_vault: AccountInfo<'info>,
+    // deposits are held here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
     system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"fee_exemption", bridge_handler.key().as_ref(), fee_exemption.exempt_account.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+    /// CHECK: only address check is needed
+    #[account(address = IX_ID)]
+    ix_sysvar: Option<UncheckedAccount<'info>>,
+    // only needed when bridging from wSOL, which is unwrapped into the signer first
+    #[account(address = native_mint::ID)]
+    wsol_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = native_mint::ID,
+    )]
+    signer_wsol_vault: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds = [b"wsol_unwrap", bridge_handler.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    /// CHECK: temporary wSOL account created and closed within the instruction
+    wsol_unwrap_vault: Option<UncheckedAccount<'info>>,
+    token_program: Option<Program<'info, Token>>,
 }
 
 impl BridgeAssetSourceChainSol<'_> {
-    pub fn bridge_asset_source_chain_sol(&mut self, amount: u64, recipient: Pubkey) -> Result<u64> {
+    pub fn bridge_asset_source_chain_sol(
+        &mut self,
+        bumps: &BridgeAssetSourceChainSolBumps,
+        amount: u64,
+        recipient: [u8; 32],
+        from_wsol: bool,
+    ) -> Result<u64> {
         require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
         require!(
             amount >= MIN_SOL_BRIDGE_AMOUNT,
             BridgeHandlerError::TooLittleSolBridgeAmount
         );
 
-        self.transfer_sol_to_fee_vault(self.bridge_handler.fee_info.bridge_asset_fee)?;
+        if from_wsol {
+            self.unwrap_wsol(bumps, amount)?;
+        }
+
+        let bridge_asset_fee = apply_fee_exemption(
+            self.chain_config.fee_info.bridge_asset_fee,
+            &self.signer.key(),
+            self.fee_exemption
+                .as_deref()
+                .map(|fee_exemption| &**fee_exemption),
+            self.ix_sysvar.as_deref(),
+        )?;
+        self.transfer_sol_to_fee_vault(bridge_asset_fee)?;
 
         // lamports auto reloads after cpi
         require!(
@@ -61,37 +131,157 @@ impl BridgeAssetSourceChainSol<'_> {
         );
 
         if self.bridge_handler.chain == Chain::Solana {
-            self.transfer_sol_to_bridge_handler(amount)?;
+            self.transfer_sol_to_sol_vault(amount)?;
         } else {
             self.burn_sol(amount)?;
         }
 
-        let nonce = self.bridge_handler.nonce;
+        let nonce = self
+            .sender_sequence
+            .allocate(bumps.sender_sequence, self.signer.key())?;
         msg!("nonce: {:?}", nonce);
-        self.bridge_handler.nonce = nonce.checked_add(1).unwrap();
         Ok(nonce)
     }
 
     pub fn issue_bridge_proof(
         &mut self,
         bumps: BridgeAssetSourceChainSolBumps,
-        bridge_proof_nonce: u64,
+        sequence: u64,
         amount: u64,
-        recipient: Pubkey,
+        recipient: [u8; 32],
+        deliver_as_wsol: bool,
+        deadline: u64,
     ) -> Result<()> {
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
         self.bridge_proof.bump = bumps.bridge_proof;
-        let mut message_data = Vec::new();
-        message_data.extend_from_slice(&self.signer.key().to_bytes());
-        message_data.extend_from_slice(&recipient.to_bytes());
-        message_data.extend_from_slice(&SOL_MINT_PUBKEY.to_bytes());
-        message_data.extend_from_slice(&amount.to_be_bytes());
-        message_data.extend_from_slice(&bridge_proof_nonce.to_be_bytes());
-        self.bridge_proof.msg_hash = hash(message_data.as_ref()).to_bytes();
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&recipient)?;
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence,
+            deadline,
+            payload: BridgePayload::Sol {
+                amount,
+                deliver_as_wsol,
+            },
+        };
+        self.bridge_proof.msg_hash = message.hash(self.chain_config.chain_kind)?;
         self.bridge_proof.user_account = self.signer.key();
         self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
         Ok(())
     }
 
+    // moves `amount` of wSOL into a temporary account and closes it to the signer, so the
+    // lamports take the native SOL path from there
+    fn unwrap_wsol(&mut self, bumps: &BridgeAssetSourceChainSolBumps, amount: u64) -> Result<()> {
+        let (
+            Some(wsol_mint),
+            Some(signer_wsol_vault),
+            Some(wsol_unwrap_vault),
+            Some(token_program),
+            Some(wsol_unwrap_vault_bump),
+        ) = (
+            &self.wsol_mint,
+            &self.signer_wsol_vault,
+            &self.wsol_unwrap_vault,
+            &self.token_program,
+            bumps.wsol_unwrap_vault,
+        )
+        else {
+            return Err(BridgeHandlerError::MissingWsolAccounts.into());
+        };
+        require!(
+            signer_wsol_vault.amount >= amount,
+            BridgeHandlerError::InsufficientAmount
+        );
+
+        let bridge_handler_key = self.bridge_handler.key();
+        let signer_key = self.signer.key();
+        let bump = [wsol_unwrap_vault_bump];
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"wsol_unwrap",
+            bridge_handler_key.as_ref(),
+            signer_key.as_ref(),
+            &bump,
+        ][..]];
+
+        // lamports sent to the address ahead of time would fail a create_account, so only
+        // top up the rent and allocate and assign the account under its seeds
+        let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+        let shortfall = rent.saturating_sub(wsol_unwrap_vault.lamports());
+        if shortfall > 0 {
+            let ctx = CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.signer.to_account_info(),
+                    to: wsol_unwrap_vault.to_account_info(),
+                },
+            );
+            transfer(ctx, shortfall)?;
+        }
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: wsol_unwrap_vault.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        allocate(ctx, TokenAccount::LEN as u64)?;
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            Assign {
+                account_to_assign: wsol_unwrap_vault.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        assign(ctx, token_program.key)?;
+
+        let ctx = CpiContext::new(
+            token_program.to_account_info(),
+            InitializeAccount3 {
+                account: wsol_unwrap_vault.to_account_info(),
+                mint: wsol_mint.to_account_info(),
+                authority: self.signer.to_account_info(),
+            },
+        );
+        initialize_account3(ctx)?;
+
+        let ctx = CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: signer_wsol_vault.to_account_info(),
+                to: wsol_unwrap_vault.to_account_info(),
+                mint: wsol_mint.to_account_info(),
+                authority: self.signer.to_account_info(),
+            },
+        );
+        transfer_checked(ctx, amount, wsol_mint.decimals)?;
+
+        let ctx = CpiContext::new(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: wsol_unwrap_vault.to_account_info(),
+                destination: self.signer.to_account_info(),
+                authority: self.signer.to_account_info(),
+            },
+        );
+        close_account(ctx)?;
+
+        msg!("unwrapped {:?} wsol", amount);
+        Ok(())
+    }
+
     fn transfer_sol_to_fee_vault(&mut self, lamports: u64) -> Result<()> {
         let ctx = CpiContext::new(
             self.system_program.to_account_info(),
@@ -104,12 +294,12 @@ impl BridgeAssetSourceChainSol<'_> {
         transfer(ctx, lamports)
     }
 
-    fn transfer_sol_to_bridge_handler(&mut self, lamports: u64) -> Result<()> {
+    fn transfer_sol_to_sol_vault(&mut self, lamports: u64) -> Result<()> {
         let ctx = CpiContext::new(
             self.system_program.to_account_info(),
             Transfer {
                 from: self.signer.to_account_info(),
-                to: self.bridge_handler.to_account_info(),
+                to: self.sol_vault.to_account_info(),
             },
         );
 
@@ -1,17 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{
-    mint_to, transfer_checked, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
-};
-
-use solana_program::hash::hash;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
 
-use crate::constants::{
-    get_whitelisted_token_price, is_token_whitelisted, MAX_ADDITIONAL_SOL_GAS,
-    MAX_GUARDIAN_SIGNATURES, SOLANA_DOLLAR_CAP_PER_EPOCH,
-    SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS,
+use crate::constants::{get_whitelisted_token_price, is_token_whitelisted, MAX_ADDITIONAL_SOL_GAS};
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProof, ChainConfig, DeliveryBitmap, GuardianInfo,
+    TokenInfo, VerifiedSignatures,
 };
-use crate::states::{BridgeProof, GuardianInfo, TokenInfo, VerifiedSignatures};
+use crate::utils::{transfer_checked_with_hook, transfer_sol_from_vault};
 use crate::{
     errors::BridgeHandlerError,
     states::{BridgeHandler, Chain},
@@ -25,10 +21,11 @@ extern "C" {
 }
 
 #[derive(Accounts)]
-#[instruction(msg_hash: [u8; 32], source_tx_id: [u8; 64])]
+#[instruction(msg_hash: [u8; 32], sequence: u64, source_tx_id: [u8; 64], sender: [u8; 32])]
 pub struct BridgeAssetTargetChain<'info> {
+    // the operator, or anyone claiming a delivery the guardians have attested
     #[account(mut)]
-    operator: Signer<'info>,
+    payer: Signer<'info>,
     #[account(
         mut,
         mint::token_program = token_program
@@ -39,19 +36,23 @@ pub struct BridgeAssetTargetChain<'info> {
     recipient: AccountInfo<'info>,
     #[account(
         init_if_needed,
-        payer = operator,
+        payer = payer,
         associated_token::authority = recipient,
         associated_token::mint = mint,
         associated_token::token_program = token_program
     )]
     recipient_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        mut,
-        has_one = operator @ BridgeHandlerError::InvalidOperator,
         seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
         bump = bridge_handler.bump
     )]
     bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
     #[account(
         mut,
         associated_token::authority = bridge_handler,
@@ -59,26 +60,46 @@ pub struct BridgeAssetTargetChain<'info> {
         associated_token::token_program = token_program
     )]
     bridge_handler_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // additional gas is paid from here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
+    // optional per-transfer receipt, replays are rejected by the delivery bitmap
     #[account(
         init,
-        payer = operator,
+        payer = payer,
         space = 8 + BridgeProof::INIT_SPACE,
-        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), hash(source_tx_id.as_ref()).to_bytes().as_ref()],
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), sequence.to_be_bytes().as_ref()],
         bump
     )]
-    bridge_proof: Box<Account<'info, BridgeProof>>,
+    bridge_proof: Option<Box<Account<'info, BridgeProof>>>,
     #[account(
-        seeds = [b"guardian_info", bridge_handler.key().as_ref()],
-        bump = guardian_info.bump
+        init_if_needed,
+        payer = payer,
+        space = 8 + DeliveryBitmap::INIT_SPACE,
+        seeds = [b"delivery_bitmap", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), DeliveryBitmap::page(sequence).to_be_bytes().as_ref()],
+        bump
     )]
+    delivery_bitmap: Box<Account<'info, DeliveryBitmap>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
     guardian_info: Box<Account<'info, GuardianInfo>>,
     #[account(
         mut,
-        close = operator,
+        close = signatures_payer,
         seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
         bump = verified_signatures.bump
     )]
     verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
     #[account(
         seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
         bump = token_info.bump
@@ -95,72 +116,81 @@ impl<'info> BridgeAssetTargetChain<'info> {
         &mut self,
         bumps: BridgeAssetTargetChainBumps,
         msg_hash: [u8; 32],
+        sequence: u64,
         source_tx_id: [u8; 64],
-        sender: Pubkey,
+        sender: [u8; 32],
         source_mint: Pubkey,
         receive_amount: u64,
-        nonce: u64,
         additional_sol_gas: u64,
+        deadline: u64,
+        remaining_accounts: &[AccountInfo<'info>],
     ) -> Result<()> {
         require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
 
         let chain = self.bridge_handler.chain;
 
+        TokenInfo::check_counterpart_chain(chain, self.chain_config.chain_id)?;
+        self.token_info
+            .check_deliverable(Clock::get()?.unix_timestamp as u64)?;
+        self.token_info.check_transfer_amount(receive_amount)?;
+
+        // the message carries the amount in the lower precision of the two mints, transfers
+        // sent from a counterpart replaced by a mapping migration keep its precision
+        let local_amount =
+            self.token_info
+                .denormalize_amount_for(chain, &source_mint, receive_amount)?;
+
         // validate instant bridge cap
         if chain == Chain::Solana && is_token_whitelisted(self.mint.key()) {
             let dollar_value =
-                get_whitelisted_token_price(self.mint.key(), receive_amount, self.mint.decimals)
+                get_whitelisted_token_price(self.mint.key(), local_amount, self.mint.decimals)
                     .ok_or(BridgeHandlerError::TokenNotExists)?;
 
-            if self.bridge_handler.instant_bridge_cap_epoch != Clock::get()?.epoch {
-                self.bridge_handler.instant_bridge_cap_remained_dollar =
-                    SOLANA_DOLLAR_CAP_PER_EPOCH;
-                self.bridge_handler.instant_bridge_cap_epoch = Clock::get()?.epoch;
-            }
-
-            if self.bridge_handler.instant_bridge_cap_remained_dollar < dollar_value as u64 {
-                let awaiting_time = (Clock::get()?.unix_timestamp as u64)
-                    .checked_sub(self.verified_signatures.created_at)
-                    .unwrap();
-                if awaiting_time < SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS {
-                    return Err(BridgeHandlerError::InstantBridgeCapExceeded.into());
-                }
-            } else {
-                self.bridge_handler.instant_bridge_cap_remained_dollar -= dollar_value as u64;
-            }
+            let signatures_age = (Clock::get()?.unix_timestamp as u64)
+                .checked_sub(self.verified_signatures.created_at)
+                .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+            self.chain_config.consume_instant_cap(
+                dollar_value as u64,
+                Clock::get()?.epoch,
+                signatures_age,
+            )?;
         }
 
-        require!(
-            !self.verified_signatures.pubkey_index.is_empty()
-                && self.verified_signatures.pubkey_index.len()
-                    <= self.guardian_info.guardians.len()
-                && self.verified_signatures.pubkey_index.len() <= MAX_GUARDIAN_SIGNATURES,
-            BridgeHandlerError::InvalidSignerCount
-        );
-
-        // verify sigs meet threshold
-        require!(
-            self.verified_signatures.pubkey_index.len()
-                >= self.bridge_handler.guardian_threshold as usize,
-            BridgeHandlerError::GuardianThresholdNotMet
-        );
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
 
         require!(
             additional_sol_gas <= MAX_ADDITIONAL_SOL_GAS,
             BridgeHandlerError::TooMuchAdditionalSolGas
         );
 
-        let mut message_data = Vec::new();
-        message_data.extend_from_slice(&sender.to_bytes());
-        message_data.extend_from_slice(&self.recipient.key().to_bytes());
-        message_data.extend_from_slice(&source_mint.to_bytes());
-        message_data.extend_from_slice(&self.mint.key().to_bytes());
-        message_data.extend_from_slice(&receive_amount.to_be_bytes());
-        message_data.extend_from_slice(&nonce.to_be_bytes());
-        message_data.extend_from_slice(&source_tx_id);
-        message_data.extend_from_slice(&additional_sol_gas.to_be_bytes());
-
-        let message: [u8; 32] = hash(message_data.as_ref()).to_bytes();
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&sender)?;
+        let message = BridgeMessage {
+            source_chain_id: self.chain_config.chain_id,
+            source_emitter: self.chain_config.emitter,
+            destination_chain_id: self.bridge_handler.chain.chain_id(),
+            destination_emitter: self.bridge_handler.key().to_bytes(),
+            sender,
+            recipient: self.recipient.key().to_bytes(),
+            sequence,
+            deadline,
+            payload: BridgePayload::Token {
+                source_mint,
+                target_mint: self.mint.key(),
+                amount: receive_amount,
+                additional_sol_gas,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
         require!(
             message == msg_hash,
             BridgeHandlerError::InvalidGuardianSignatureMessage
@@ -170,55 +200,74 @@ impl<'info> BridgeAssetTargetChain<'info> {
             || (chain == Chain::Solayer && self.token_info.is_solayer_native_token)
         {
             require!(
-                self.bridge_handler_vault.amount >= receive_amount,
+                self.bridge_handler_vault.amount >= local_amount,
                 BridgeHandlerError::InsufficientFunds
             );
-            msg!("transfer {} token to recipient", receive_amount);
-            self.transfer_token(receive_amount, self.recipient_vault.to_account_info())?;
+            msg!("transfer {} token to recipient", local_amount);
+            self.transfer_token(
+                local_amount,
+                self.recipient_vault.to_account_info(),
+                remaining_accounts,
+            )?;
         } else if (chain == Chain::Solana && !self.token_info.is_solana_native_token)
             || (chain == Chain::Solayer && !self.token_info.is_solayer_native_token)
         {
-            msg!("mint {} token to recipient", receive_amount);
-            self.mint_token(receive_amount, self.recipient_vault.to_account_info())?;
+            msg!("mint {} token to recipient", local_amount);
+            self.mint_token(local_amount, self.recipient_vault.to_account_info())?;
         } else {
             require!(false, BridgeHandlerError::InvalidTokenInfo);
         }
 
-        self.bridge_proof.bump = bumps.bridge_proof;
-        self.bridge_proof.msg_hash = message;
-        self.bridge_proof.tx_id = source_tx_id;
-        self.bridge_proof.user_account = self.recipient.key();
-        self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
+        let recipient = self.recipient.key();
+        let payer = self.payer.key();
+        let receipt = self
+            .bridge_proof
+            .as_deref_mut()
+            .map(|bridge_proof| &mut **bridge_proof)
+            .zip(bumps.bridge_proof);
+        self.delivery_bitmap.record_delivery(
+            bumps.delivery_bitmap,
+            sequence,
+            receipt,
+            message,
+            source_tx_id,
+            recipient,
+            payer,
+        )?;
 
         if additional_sol_gas > 0 {
             if chain == Chain::Solayer {
                 self.mint_sol_to_recipient(additional_sol_gas)?;
             } else {
-                self.transfer_sol_to_recipient(additional_sol_gas)?;
+                self.transfer_sol_to_recipient(bumps.sol_vault, additional_sol_gas)?;
             }
         }
 
         Ok(())
     }
 
-    fn transfer_token(&mut self, amount: u64, target_vault: AccountInfo<'info>) -> Result<()> {
+    fn transfer_token(
+        &mut self,
+        amount: u64,
+        target_vault: AccountInfo<'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         let bump = [self.bridge_handler.bump];
         let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
         let signer_seeds: [&[&[u8]]; 1] =
             [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
 
-        let ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            TransferChecked {
-                from: self.bridge_handler_vault.to_account_info(),
-                to: target_vault,
-                mint: self.mint.to_account_info(),
-                authority: self.bridge_handler.to_account_info(),
-            },
+        transfer_checked_with_hook(
+            &self.token_program.to_account_info(),
+            self.bridge_handler_vault.to_account_info(),
+            self.mint.to_account_info(),
+            target_vault,
+            self.bridge_handler.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
             &signer_seeds[..],
-        );
-
-        transfer_checked(ctx, amount, self.mint.decimals)
+        )
     }
 
     fn mint_token(&mut self, amount: u64, target_vault: AccountInfo<'info>) -> Result<()> {
@@ -274,20 +323,14 @@ impl<'info> BridgeAssetTargetChain<'info> {
         Ok(())
     }
 
-    fn transfer_sol_to_recipient(&mut self, lamports: u64) -> Result<()> {
-        require!(
-            Rent::get()?.minimum_balance(self.bridge_handler.to_account_info().data_len())
-                + lamports
-                <= self.bridge_handler.to_account_info().lamports(),
-            BridgeHandlerError::InsufficientFunds
-        );
-
-        **self
-            .bridge_handler
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= lamports;
-        **self.recipient.try_borrow_mut_lamports()? += lamports;
-
-        Ok(())
+    fn transfer_sol_to_recipient(&mut self, sol_vault_bump: u8, lamports: u64) -> Result<()> {
+        transfer_sol_from_vault(
+            &self.system_program.to_account_info(),
+            &self.sol_vault.to_account_info(),
+            &self.recipient.to_account_info(),
+            &self.bridge_handler.key(),
+            sol_vault_bump,
+            lamports,
+        )
     }
 }
@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
-use solana_program::hash::hash;
-use solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{
+    spl_token::native_mint, sync_native, Mint, SyncNative, Token, TokenAccount,
+};
 
-use crate::constants::{
-    MAX_GUARDIAN_SIGNATURES, SOLANA_DOLLAR_CAP_PER_EPOCH, SOLANA_DOLLAR_VALUE,
-    SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS, SOL_MINT_PUBKEY,
+use crate::constants::{get_sol_dollar_value, MIN_SOL_BRIDGE_AMOUNT};
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProof, ChainConfig, DeliveryBitmap, GuardianInfo,
+    VerifiedSignatures,
 };
-use crate::states::{BridgeProof, GuardianInfo, VerifiedSignatures};
+use crate::utils::{instruction_account_index, transfer_sol_from_vault};
 use crate::{
     errors::BridgeHandlerError,
     states::{BridgeHandler, Chain},
@@ -20,148 +23,230 @@ extern "C" {
 }
 
 #[derive(Accounts)]
-#[instruction(msg_hash: [u8; 32], source_tx_id: [u8; 64])]
+#[instruction(msg_hash: [u8; 32], sequence: u64, source_tx_id: [u8; 64], sender: [u8; 32])]
 pub struct BridgeAssetTargetChainSol<'info> {
+    // the operator, or anyone claiming a delivery the guardians have attested
     #[account(mut)]
-    operator: Signer<'info>,
+    payer: Signer<'info>,
     /// CHECKED: checks will be performed agsint signature with hash
     #[account(mut)]
     recipient: AccountInfo<'info>,
     #[account(
-        mut,
-        has_one = operator @ BridgeHandlerError::InvalidOperator,
         seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
         bump = bridge_handler.bump
     )]
     bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    // optional per-transfer receipt, replays are rejected by the delivery bitmap
     #[account(
         init,
-        payer = operator,
+        payer = payer,
         space = 8 + BridgeProof::INIT_SPACE,
-        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), hash(source_tx_id.as_ref()).to_bytes().as_ref()],
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), sequence.to_be_bytes().as_ref()],
         bump
     )]
-    bridge_proof: Box<Account<'info, BridgeProof>>,
+    bridge_proof: Option<Box<Account<'info, BridgeProof>>>,
     #[account(
-        seeds = [b"guardian_info", bridge_handler.key().as_ref()],
-        bump = guardian_info.bump
+        init_if_needed,
+        payer = payer,
+        space = 8 + DeliveryBitmap::INIT_SPACE,
+        seeds = [b"delivery_bitmap", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), DeliveryBitmap::page(sequence).to_be_bytes().as_ref()],
+        bump
     )]
+    delivery_bitmap: Box<Account<'info, DeliveryBitmap>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
     guardian_info: Box<Account<'info, GuardianInfo>>,
     #[account(
         mut,
-        close = operator,
+        close = signatures_payer,
         seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
         bump = verified_signatures.bump
     )]
     verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    // deliveries are paid from here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
     system_program: Program<'info, System>,
+    // only needed when the sender asked for delivery as wSOL
+    #[account(address = native_mint::ID)]
+    wsol_mint: Option<Box<Account<'info, Mint>>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::authority = recipient,
+        associated_token::mint = wsol_mint,
+        associated_token::token_program = token_program
+    )]
+    recipient_wsol_vault: Option<Box<Account<'info, TokenAccount>>>,
+    token_program: Option<Program<'info, Token>>,
+    associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
-impl BridgeAssetTargetChainSol<'_> {
+impl<'info> BridgeAssetTargetChainSol<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn bridge_asset_target_chain_sol(
         &mut self,
         bumps: BridgeAssetTargetChainSolBumps,
         msg_hash: [u8; 32],
+        sequence: u64,
         source_tx_id: [u8; 64],
-        sender: Pubkey,
+        sender: [u8; 32],
         receive_amount: u64,
-        nonce: u64,
+        deliver_as_wsol: bool,
+        deadline: u64,
     ) -> Result<()> {
         require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
+
+        // SOL has no token info, its only limit is the minimum the source chain enforces
+        require!(
+            receive_amount >= MIN_SOL_BRIDGE_AMOUNT,
+            BridgeHandlerError::TooLittleSolBridgeAmount
+        );
 
         let chain = self.bridge_handler.chain;
 
         // validate instant bridge cap
         if chain == Chain::Solana {
-            let dollar_value =
-                receive_amount as f64 / LAMPORTS_PER_SOL as f64 * SOLANA_DOLLAR_VALUE as f64;
-
-            if self.bridge_handler.instant_bridge_cap_epoch != Clock::get()?.epoch {
-                self.bridge_handler.instant_bridge_cap_remained_dollar =
-                    SOLANA_DOLLAR_CAP_PER_EPOCH;
-                self.bridge_handler.instant_bridge_cap_epoch = Clock::get()?.epoch;
-            }
-
-            if self.bridge_handler.instant_bridge_cap_remained_dollar < dollar_value as u64 {
-                let awaiting_time = (Clock::get()?.unix_timestamp as u64)
-                    .checked_sub(self.verified_signatures.created_at)
-                    .unwrap();
-                if awaiting_time < SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS {
-                    return Err(BridgeHandlerError::InstantBridgeCapExceeded.into());
-                }
-            } else {
-                self.bridge_handler.instant_bridge_cap_remained_dollar =
-                    (self.bridge_handler.instant_bridge_cap_remained_dollar)
-                        .checked_sub(dollar_value as u64)
-                        .unwrap();
-            }
+            let signatures_age = (Clock::get()?.unix_timestamp as u64)
+                .checked_sub(self.verified_signatures.created_at)
+                .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+            self.chain_config.consume_instant_cap(
+                get_sol_dollar_value(receive_amount) as u64,
+                Clock::get()?.epoch,
+                signatures_age,
+            )?;
         }
 
-        require!(
-            !self.verified_signatures.pubkey_index.is_empty()
-                && self.verified_signatures.pubkey_index.len()
-                    <= self.guardian_info.guardians.len()
-                && self.verified_signatures.pubkey_index.len() <= MAX_GUARDIAN_SIGNATURES,
-            BridgeHandlerError::InvalidSignerCount
-        );
-
-        // verify sigs meet threshold
-        require!(
-            self.verified_signatures.pubkey_index.len()
-                >= self.bridge_handler.guardian_threshold as usize,
-            BridgeHandlerError::GuardianThresholdNotMet
-        );
-
-        let mut message_data = Vec::new();
-        message_data.extend_from_slice(&sender.to_bytes());
-        message_data.extend_from_slice(&self.recipient.key().to_bytes());
-        message_data.extend_from_slice(&SOL_MINT_PUBKEY.to_bytes());
-        message_data.extend_from_slice(&receive_amount.to_be_bytes());
-        message_data.extend_from_slice(&nonce.to_be_bytes());
-        message_data.extend_from_slice(&source_tx_id);
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
 
-        let message: [u8; 32] = hash(message_data.as_ref()).to_bytes();
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&sender)?;
+        let message = BridgeMessage {
+            source_chain_id: self.chain_config.chain_id,
+            source_emitter: self.chain_config.emitter,
+            destination_chain_id: self.bridge_handler.chain.chain_id(),
+            destination_emitter: self.bridge_handler.key().to_bytes(),
+            sender,
+            recipient: self.recipient.key().to_bytes(),
+            sequence,
+            deadline,
+            payload: BridgePayload::Sol {
+                amount: receive_amount,
+                deliver_as_wsol,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
         require!(
             message == msg_hash,
             BridgeHandlerError::InvalidGuardianSignatureMessage
         );
 
-        self.bridge_proof.bump = bumps.bridge_proof;
-        self.bridge_proof.msg_hash = message;
-        self.bridge_proof.tx_id = source_tx_id;
-        self.bridge_proof.user_account = self.recipient.key();
-        self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
+        let recipient = self.recipient.key();
+        let payer = self.payer.key();
+        let receipt = self
+            .bridge_proof
+            .as_deref_mut()
+            .map(|bridge_proof| &mut **bridge_proof)
+            .zip(bumps.bridge_proof);
+        self.delivery_bitmap.record_delivery(
+            bumps.delivery_bitmap,
+            sequence,
+            receipt,
+            message,
+            source_tx_id,
+            recipient,
+            payer,
+        )?;
 
-        if chain == Chain::Solayer {
-            self.mint_sol_to_recipient(receive_amount)?;
+        if deliver_as_wsol {
+            let (Some(recipient_wsol_vault), Some(token_program)) =
+                (&self.recipient_wsol_vault, &self.token_program)
+            else {
+                return Err(BridgeHandlerError::MissingWsolAccounts.into());
+            };
+            let recipient_wsol_vault = recipient_wsol_vault.to_account_info();
+            let token_program = token_program.to_account_info();
+
+            // lamports of a native token account only count as wSOL after a sync
+            if chain == Chain::Solayer {
+                let account_idx = instruction_account_index(self, recipient_wsol_vault.key)?;
+                self.mint_sol_to(recipient_wsol_vault.clone(), account_idx, receive_amount)?;
+            } else {
+                self.transfer_sol_to(
+                    recipient_wsol_vault.clone(),
+                    bumps.sol_vault,
+                    receive_amount,
+                )?;
+            }
+
+            let ctx = CpiContext::new(
+                token_program,
+                SyncNative {
+                    account: recipient_wsol_vault,
+                },
+            );
+            sync_native(ctx)?;
+        } else if chain == Chain::Solayer {
+            let account_idx = instruction_account_index(self, self.recipient.key)?;
+            self.mint_sol_to(
+                self.recipient.to_account_info(),
+                account_idx,
+                receive_amount,
+            )?;
         } else {
-            self.transfer_sol_to_recipient(receive_amount)?;
+            self.transfer_sol_to(
+                self.recipient.to_account_info(),
+                bumps.sol_vault,
+                receive_amount,
+            )?;
         }
 
         Ok(())
     }
 
     #[cfg(feature = "solayer")]
-    fn mint_sol_to_recipient(&mut self, lamports: u64) -> Result<()> {
+    #[cfg_attr(not(target_os = "solana"), allow(unused_variables))]
+    fn mint_sol_to(&mut self, to: AccountInfo, account_idx: u64, lamports: u64) -> Result<()> {
         #[cfg(target_os = "solana")]
-        let result = unsafe { sol_mint_native_sol(lamports, 1) };
+        let result = unsafe { sol_mint_native_sol(lamports, account_idx) };
         #[cfg(not(target_os = "solana"))]
         let result = 0;
 
         match result {
             0 => {
-                msg!(
-                    "SUCCESS: Minted {} lamports to {}",
-                    lamports,
-                    self.recipient.key()
-                );
-                self.recipient.add_lamports(lamports)?;
+                msg!("SUCCESS: Minted {} lamports to {}", lamports, to.key());
+                to.add_lamports(lamports)?;
             }
             _ => {
                 msg!(
                     "ERROR: Failed to mint {} lamports to {}",
                     lamports,
-                    self.recipient.key()
+                    to.key()
                 );
                 return Err(BridgeHandlerError::FailToMintSol.into());
             }
@@ -170,25 +255,24 @@ impl BridgeAssetTargetChainSol<'_> {
     }
 
     #[cfg(not(feature = "solayer"))]
-    fn mint_sol_to_recipient(&mut self, _lamports: u64) -> Result<()> {
+    fn mint_sol_to(&mut self, _to: AccountInfo, _account_idx: u64, _lamports: u64) -> Result<()> {
         require!(false, BridgeHandlerError::InvalidOSForMintingSol);
         Ok(())
     }
 
-    fn transfer_sol_to_recipient(&mut self, lamports: u64) -> Result<()> {
-        require!(
-            Rent::get()?.minimum_balance(self.bridge_handler.to_account_info().data_len())
-                + lamports
-                <= self.bridge_handler.to_account_info().lamports(),
-            BridgeHandlerError::InsufficientFunds
-        );
-
-        **self
-            .bridge_handler
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= lamports;
-        **self.recipient.try_borrow_mut_lamports()? += lamports;
-
-        Ok(())
+    fn transfer_sol_to(
+        &mut self,
+        to: AccountInfo<'info>,
+        sol_vault_bump: u8,
+        lamports: u64,
+    ) -> Result<()> {
+        transfer_sol_from_vault(
+            &self.system_program.to_account_info(),
+            &self.sol_vault.to_account_info(),
+            &to,
+            &self.bridge_handler.key(),
+            sol_vault_bump,
+            lamports,
+        )
     }
 }
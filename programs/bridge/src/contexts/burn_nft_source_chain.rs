@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::metadata::{burn_nft, BurnNft, Metadata};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, NftInfo, NftMetadata,
+    SenderSequence,
+};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct BurnNftSourceChain<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(mut)]
+    mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = mint,
+    )]
+    signer_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        has_one = fee_vault,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        mut,
+        constraint = nft_info.wrapped_mint == mint.key() @ BridgeHandlerError::InvalidNft,
+        constraint = nft_info.native_chain_id == chain_config.chain_id @ BridgeHandlerError::InvalidCounterpartChain,
+        seeds = [b"nft_info", bridge_handler.key().as_ref(), nft_info.native_mint.as_ref()],
+        bump = nft_info.bump
+    )]
+    nft_info: Box<Account<'info, NftInfo>>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&mint.key()).0
+    )]
+    /// CHECK: This is the metadata account
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&mint.key()).0
+    )]
+    /// CHECK: This is the master edition account
+    master_edition: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"nft_collection", bridge_handler.key().as_ref()],
+        bump
+    )]
+    /// CHECK: mint of the bridge collection
+    collection_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+    )]
+    /// CHECK: This is the metadata account of the bridge collection
+    collection_metadata: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SenderSequence::INIT_SPACE,
+        seeds = [b"sender_sequence", bridge_handler.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    sender_sequence: Box<Account<'info, SenderSequence>>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + BridgeProofSourceChain::INIT_SPACE,
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sender_sequence.next_sequence.to_be_bytes().as_ref()],
+        bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    #[account(mut)]
+    /// CHECK: no check needed other than address check
+    fee_vault: AccountInfo<'info>,
+    token_metadata_program: Program<'info, Metadata>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+}
+
+impl BurnNftSourceChain<'_> {
+    pub fn burn_nft_source_chain(
+        &mut self,
+        bumps: BurnNftSourceChainBumps,
+        recipient: [u8; 32],
+        deadline: u64,
+    ) -> Result<u64> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
+        require!(
+            self.signer_vault.amount == 1,
+            BridgeHandlerError::InsufficientAmount
+        );
+
+        // the message describes the native NFT, so it names the native collection
+        let mut nft_metadata = NftMetadata::from_metaplex(&self.metadata, &self.mint.key())?;
+        nft_metadata.collection = self.nft_info.native_collection;
+
+        self.transfer_sol_to_fee_vault(self.chain_config.fee_info.bridge_asset_fee)?;
+
+        let ctx = CpiContext::new(
+            self.token_metadata_program.to_account_info(),
+            BurnNft {
+                metadata: self.metadata.to_account_info(),
+                owner: self.signer.to_account_info(),
+                mint: self.mint.to_account_info(),
+                token: self.signer_vault.to_account_info(),
+                edition: self.master_edition.to_account_info(),
+                spl_token: self.token_program.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(vec![self.collection_metadata.to_account_info()]);
+        burn_nft(ctx, Some(self.collection_metadata.key()))?;
+
+        msg!(
+            "burnt wrapped nft {:?} of {:?} for {:?}",
+            self.mint.key(),
+            self.nft_info.native_mint,
+            recipient
+        );
+        self.nft_info.wrapped_mint = Pubkey::default();
+        self.nft_info.generation = self
+            .nft_info
+            .generation
+            .checked_add(1)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&recipient)?;
+        let nonce = self
+            .sender_sequence
+            .allocate(bumps.sender_sequence, self.signer.key())?;
+        msg!("nonce: {:?}", nonce);
+
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence: nonce,
+            deadline,
+            payload: BridgePayload::Nft {
+                native_mint: self.nft_info.native_mint,
+                metadata: nft_metadata,
+            },
+        };
+        self.bridge_proof.bump = bumps.bridge_proof;
+        self.bridge_proof.msg_hash = message.hash(self.chain_config.chain_kind)?;
+        self.bridge_proof.user_account = self.signer.key();
+        self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
+
+        Ok(nonce)
+    }
+
+    fn transfer_sol_to_fee_vault(&mut self, lamports: u64) -> Result<()> {
+        let ctx = CpiContext::new(
+            self.system_program.to_account_info(),
+            Transfer {
+                from: self.signer.to_account_info(),
+                to: self.fee_vault.to_account_info(),
+            },
+        );
+
+        transfer(ctx, lamports)
+    }
+}
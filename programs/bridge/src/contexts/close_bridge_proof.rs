@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{BridgeProof, ChainConfig, DeliveryBitmap};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Compacts a delivery receipt into the delivery bitmap, so replays stay rejected once the
+// BridgeProof is gone.
+#[derive(Accounts)]
+#[instruction(sender: [u8; 32], sequence: u64)]
+pub struct CloseBridgeProof<'info> {
+    #[account(mut)]
+    operator: Signer<'info>,
+    #[account(
+        has_one = operator @ BridgeHandlerError::InvalidOperator,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        mut,
+        close = receipt_payer,
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), sequence.to_be_bytes().as_ref()],
+        bump = bridge_proof.bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProof>>,
+    #[account(mut, address = bridge_proof.payer @ BridgeHandlerError::InvalidReceiptPayer)]
+    /// CHECK: only receives the rent of the receipt, checked against bridge_proof
+    receipt_payer: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + DeliveryBitmap::INIT_SPACE,
+        seeds = [b"delivery_bitmap", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), DeliveryBitmap::page(sequence).to_be_bytes().as_ref()],
+        bump
+    )]
+    delivery_bitmap: Box<Account<'info, DeliveryBitmap>>,
+    system_program: Program<'info, System>,
+}
+
+impl CloseBridgeProof<'_> {
+    pub fn close_bridge_proof(
+        &mut self,
+        bumps: CloseBridgeProofBumps,
+        sender: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        // receipts written before the bitmap existed are not marked yet
+        if !self.delivery_bitmap.is_delivered(sequence) {
+            self.delivery_bitmap
+                .mark_delivered(bumps.delivery_bitmap, sequence)?;
+        }
+
+        msg!("closing bridge proof {:?} from {:?}", sequence, sender);
+        Ok(())
+    }
+}
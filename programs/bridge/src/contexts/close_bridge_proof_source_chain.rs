@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SOURCE_PROOF_FINALITY_PERIOD_SECONDS;
+use crate::states::{BridgeMessage, BridgeProofSourceChain, GuardianInfo, VerifiedSignatures};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct CloseBridgeProofSourceChain<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    // matched by owner instead of seeds, so proofs created under the old nonce seeds close too
+    #[account(
+        mut,
+        close = signer,
+        constraint = bridge_proof.user_account == signer.key() @ BridgeHandlerError::Unauthorized
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    #[account(
+        seeds = [b"guardian_info", bridge_handler.key().as_ref()],
+        bump = guardian_info.bump
+    )]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    // guardians attesting that the message was delivered on the target chain
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), BridgeMessage::delivery_hash(&bridge_proof.msg_hash).as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+}
+
+impl CloseBridgeProofSourceChain<'_> {
+    pub fn close_bridge_proof_source_chain(&mut self) -> Result<()> {
+        let finalized_at = self
+            .bridge_proof
+            .created_at
+            .checked_add(SOURCE_PROOF_FINALITY_PERIOD_SECONDS)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        require!(
+            Clock::get()?.unix_timestamp as u64 >= finalized_at,
+            BridgeHandlerError::FinalityPeriodNotElapsed
+        );
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        msg!(
+            "closing bridge proof {:?} of {:?}",
+            self.bridge_proof.key(),
+            self.signer.key()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use solana_program::hash::hash;
+
+use crate::states::BridgeProof;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Closes a delivery receipt written before receipts were seeded by source chain, emitter,
+// sender and sequence. Those deliveries were replay protected by the receipt alone, but
+// their messages predate the current message format and can't be attested again. The
+// receipts were paid by the operator, and don't deserialize as the current BridgeProof.
+#[derive(Accounts)]
+#[instruction(source_tx_id: [u8; 64])]
+pub struct CloseLegacyBridgeProof<'info> {
+    #[account(mut)]
+    operator: Signer<'info>,
+    #[account(
+        has_one = operator @ BridgeHandlerError::InvalidOperator,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), hash(source_tx_id.as_ref()).to_bytes().as_ref()],
+        bump
+    )]
+    /// CHECK: owner, discriminator and the legacy layout are checked before closing
+    bridge_proof: UncheckedAccount<'info>,
+}
+
+impl CloseLegacyBridgeProof<'_> {
+    pub fn close_legacy_bridge_proof(&mut self, source_tx_id: [u8; 64]) -> Result<()> {
+        require_keys_eq!(
+            *self.bridge_proof.owner,
+            crate::ID,
+            BridgeHandlerError::InvalidAccountLayout
+        );
+        {
+            let data = self.bridge_proof.try_borrow_data()?;
+            require!(
+                data.len() == BridgeProof::LEGACY_LEN && data[..8] == BridgeProof::DISCRIMINATOR,
+                BridgeHandlerError::InvalidAccountLayout
+            );
+        }
+
+        msg!("closing legacy bridge proof of {:?}", source_tx_id);
+
+        let bridge_proof = self.bridge_proof.to_account_info();
+        let lamports = bridge_proof.lamports();
+        bridge_proof.sub_lamports(lamports)?;
+        self.operator.add_lamports(lamports)?;
+        bridge_proof.assign(&System::id());
+        bridge_proof.realloc(0, false)?;
+        Ok(())
+    }
+}
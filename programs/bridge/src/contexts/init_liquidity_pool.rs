@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::states::{LiquidityPool, TokenInfo};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct InitLiquidityPool<'info> {
+    #[account(mut)]
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = token_info.is_pool_mode() @ BridgeHandlerError::NotPoolModeToken,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    #[account(
+        init,
+        payer = token_admin,
+        space = 8 + LiquidityPool::INIT_SPACE,
+        seeds = [b"liquidity_pool", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    liquidity_pool: Box<Account<'info, LiquidityPool>>,
+    #[account(
+        init,
+        payer = token_admin,
+        mint::authority = bridge_handler,
+        mint::token_program = token_program,
+        mint::decimals = mint.decimals,
+        seeds = [b"lp_mint", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl InitLiquidityPool<'_> {
+    pub fn init_liquidity_pool(&mut self, liquidity_pool_bump: u8) -> Result<()> {
+        msg!(
+            "initializing liquidity pool of {:?} with lp mint {:?}",
+            self.mint.key(),
+            self.lp_mint.key()
+        );
+
+        self.liquidity_pool.bump = liquidity_pool_bump;
+        self.liquidity_pool.mint = self.mint.key();
+        self.liquidity_pool.lp_mint = self.lp_mint.key();
+        self.liquidity_pool.total_liquidity = 0;
+        self.liquidity_pool.total_shares = 0;
+        self.liquidity_pool.created_at = Clock::get()?.unix_timestamp as u64;
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{CollectionDetails, DataV2},
+        CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct InitNftCollection<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        init,
+        payer = manager,
+        mint::authority = bridge_handler,
+        mint::freeze_authority = bridge_handler,
+        mint::decimals = 0,
+        seeds = [b"nft_collection", bridge_handler.key().as_ref()],
+        bump
+    )]
+    collection_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = manager,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = collection_mint,
+    )]
+    bridge_handler_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+    )]
+    /// CHECK: This is the metadata account
+    collection_metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+    )]
+    /// CHECK: This is the master edition account
+    collection_master_edition: UncheckedAccount<'info>,
+    token_metadata_program: Program<'info, Metadata>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+impl InitNftCollection<'_> {
+    pub fn init_nft_collection(&mut self, name: String, symbol: String, uri: String) -> Result<()> {
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.collection_mint.to_account_info(),
+                to: self.bridge_handler_vault.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        mint_to(ctx, 1)?;
+
+        let collection_data = DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: self.collection_metadata.to_account_info(),
+                mint: self.collection_mint.to_account_info(),
+                mint_authority: self.bridge_handler.to_account_info(),
+                payer: self.manager.to_account_info(),
+                update_authority: self.bridge_handler.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        create_metadata_accounts_v3(
+            ctx,
+            collection_data,
+            true,
+            true,
+            Some(CollectionDetails::V1 { size: 0 }),
+        )?;
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: self.collection_master_edition.to_account_info(),
+                mint: self.collection_mint.to_account_info(),
+                update_authority: self.bridge_handler.to_account_info(),
+                mint_authority: self.bridge_handler.to_account_info(),
+                payer: self.manager.to_account_info(),
+                metadata: self.collection_metadata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        create_master_edition_v3(ctx, Some(0))?;
+
+        msg!(
+            "initialized bridge nft collection {:?}",
+            self.collection_mint.key()
+        );
+        Ok(())
+    }
+}
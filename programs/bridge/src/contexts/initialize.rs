@@ -45,7 +45,6 @@ impl Initialize<'_> {
         self.bridge_handler.bump = bumps.bridge_handler;
         self.bridge_handler.init_nonce = init_nonce;
         self.bridge_handler.pause = false;
-        self.bridge_handler.nonce = 0;
         self.bridge_handler.chain = chain;
         self.bridge_handler.fee_vault = self.fee_vault.key();
         self.bridge_handler.manager = self.manager.key();
@@ -55,6 +54,8 @@ impl Initialize<'_> {
         self.bridge_handler.instant_bridge_cap_remained_dollar = SOLANA_DOLLAR_CAP_PER_EPOCH;
         self.bridge_handler.instant_bridge_cap_epoch = Clock::get()?.epoch;
         self.bridge_handler.fee_info = FixedFeeInfo::default();
+        self.bridge_handler.strict_mint_extensions = false;
+        self.bridge_handler.token_admin = self.manager.key();
 
         self.guardian_info.bump = bumps.guardian_info;
         self.guardian_info.guardians = vec![];
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+use mpl_token_metadata::accounts::MasterEdition;
+use mpl_token_metadata::types::Key as MetaplexKey;
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, NftMetadata, SenderSequence,
+};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct LockNftSourceChain<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = mint,
+    )]
+    signer_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        has_one = fee_vault,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = mint,
+    )]
+    bridge_handler_vault: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Metaplex metadata of the mint, address is checked when it is used
+    metadata: UncheckedAccount<'info>,
+    #[account(address = MasterEdition::find_pda(&mint.key()).0 @ BridgeHandlerError::InvalidNft)]
+    /// CHECK: Metaplex master edition of the mint, deserialized when it is used
+    master_edition: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + SenderSequence::INIT_SPACE,
+        seeds = [b"sender_sequence", bridge_handler.key().as_ref(), signer.key().as_ref()],
+        bump
+    )]
+    sender_sequence: Box<Account<'info, SenderSequence>>,
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + BridgeProofSourceChain::INIT_SPACE,
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sender_sequence.next_sequence.to_be_bytes().as_ref()],
+        bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    #[account(mut)]
+    /// CHECK: no check needed other than address check
+    fee_vault: AccountInfo<'info>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl LockNftSourceChain<'_> {
+    pub fn lock_nft_source_chain(
+        &mut self,
+        bumps: LockNftSourceChainBumps,
+        recipient: [u8; 32],
+        deadline: u64,
+    ) -> Result<u64> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
+        require!(
+            self.mint.decimals == 0 && self.mint.supply == 1,
+            BridgeHandlerError::InvalidNft
+        );
+        require!(
+            self.signer_vault.amount == 1,
+            BridgeHandlerError::InsufficientAmount
+        );
+
+        // only master editions are bridged, prints and fungible assets share mint and supply
+        // rules with them but are not unique
+        require_keys_eq!(
+            *self.master_edition.owner,
+            mpl_token_metadata::ID,
+            BridgeHandlerError::InvalidNft
+        );
+        let master_edition = MasterEdition::from_bytes(&self.master_edition.try_borrow_data()?)
+            .map_err(|_| BridgeHandlerError::InvalidNft)?;
+        require!(
+            master_edition.key == MetaplexKey::MasterEditionV2,
+            BridgeHandlerError::InvalidNft
+        );
+
+        let nft_metadata = NftMetadata::from_metaplex(&self.metadata, &self.mint.key())?;
+
+        // wrapped NFTs are verified items of the bridge collection and go back through
+        // burn_nft_source_chain instead
+        let (bridge_collection, _) = Pubkey::find_program_address(
+            &[b"nft_collection", self.bridge_handler.key().as_ref()],
+            &crate::ID,
+        );
+        require!(
+            nft_metadata.collection != Some(bridge_collection),
+            BridgeHandlerError::InvalidNft
+        );
+
+        self.transfer_sol_to_fee_vault(self.chain_config.fee_info.bridge_asset_fee)?;
+
+        let ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.signer_vault.to_account_info(),
+                to: self.bridge_handler_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.signer.to_account_info(),
+            },
+        );
+        transfer_checked(ctx, 1, 0)?;
+
+        msg!("locking nft {:?} for {:?}", self.mint.key(), recipient);
+
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&recipient)?;
+        let nonce = self
+            .sender_sequence
+            .allocate(bumps.sender_sequence, self.signer.key())?;
+        msg!("nonce: {:?}", nonce);
+
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence: nonce,
+            deadline,
+            payload: BridgePayload::Nft {
+                native_mint: self.mint.key(),
+                metadata: nft_metadata,
+            },
+        };
+        self.bridge_proof.bump = bumps.bridge_proof;
+        self.bridge_proof.msg_hash = message.hash(self.chain_config.chain_kind)?;
+        self.bridge_proof.user_account = self.signer.key();
+        self.bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
+
+        Ok(nonce)
+    }
+
+    fn transfer_sol_to_fee_vault(&mut self, lamports: u64) -> Result<()> {
+        let ctx = CpiContext::new(
+            self.system_program.to_account_info(),
+            Transfer {
+                from: self.signer.to_account_info(),
+                to: self.fee_vault.to_account_info(),
+            },
+        );
+
+        transfer(ctx, lamports)
+    }
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, mint_to, Burn, Mint, MintTo, TokenAccount, TokenInterface,
+};
+
+use crate::states::{LiquidityPool, TokenInfo};
+use crate::utils::transfer_checked_with_hook;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct ManageLiquidity<'info> {
+    #[account(mut)]
+    provider: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    #[account(
+        mut,
+        has_one = lp_mint,
+        seeds = [b"liquidity_pool", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = liquidity_pool.bump
+    )]
+    liquidity_pool: Box<Account<'info, LiquidityPool>>,
+    #[account(mut)]
+    lp_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::authority = provider,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    provider_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::authority = provider,
+        associated_token::mint = lp_mint,
+        associated_token::token_program = token_program
+    )]
+    provider_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = provider,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program
+    )]
+    bridge_handler_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_program: Interface<'info, TokenInterface>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> ManageLiquidity<'info> {
+    pub fn deposit_liquidity(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        self.token_info.check_bridgeable()?;
+        require!(
+            amount > 0 && self.provider_vault.amount >= amount,
+            BridgeHandlerError::InsufficientAmount
+        );
+
+        // the pool is credited with what reaches the vault, after any transfer fee
+        let vault_amount_before = self.bridge_handler_vault.amount;
+        transfer_checked_with_hook(
+            &self.token_program.to_account_info(),
+            self.provider_vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.bridge_handler_vault.to_account_info(),
+            self.provider.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            &[],
+        )?;
+        self.bridge_handler_vault.reload()?;
+
+        let received_amount = self
+            .bridge_handler_vault
+            .amount
+            .checked_sub(vault_amount_before)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        let shares = self.liquidity_pool.shares_for_deposit(received_amount)?;
+        require!(shares > 0, BridgeHandlerError::InsufficientAmount);
+        self.liquidity_pool.add_liquidity(received_amount, shares)?;
+
+        msg!(
+            "depositing {:?} liquidity of {:?}, shares: {:?}",
+            received_amount,
+            self.mint.key(),
+            shares
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.lp_mint.to_account_info(),
+                to: self.provider_lp_vault.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        mint_to(ctx, shares)
+    }
+
+    // withdrawals stay open for delisted and paused tokens so providers can always exit
+    pub fn withdraw_liquidity(
+        &mut self,
+        shares: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            shares > 0 && self.provider_lp_vault.amount >= shares,
+            BridgeHandlerError::InsufficientAmount
+        );
+        let amount = self.liquidity_pool.amount_for_shares(shares)?;
+        require!(amount > 0, BridgeHandlerError::InsufficientAmount);
+        // liquidity that already left through deliveries can't be withdrawn until it is
+        // bridged back in
+        require!(
+            self.bridge_handler_vault.amount >= amount,
+            BridgeHandlerError::InsufficientFunds
+        );
+        self.liquidity_pool.remove_liquidity(amount, shares)?;
+
+        msg!(
+            "withdrawing {:?} liquidity of {:?} for {:?} shares",
+            amount,
+            self.mint.key(),
+            shares
+        );
+
+        let ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Burn {
+                mint: self.lp_mint.to_account_info(),
+                from: self.provider_lp_vault.to_account_info(),
+                authority: self.provider.to_account_info(),
+            },
+        );
+        burn(ctx, shares)?;
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        transfer_checked_with_hook(
+            &self.token_program.to_account_info(),
+            self.bridge_handler_vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.provider_vault.to_account_info(),
+            self.bridge_handler.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            &signer_seeds[..],
+        )
+    }
+}
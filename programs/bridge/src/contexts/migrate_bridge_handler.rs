@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BridgeHandlerError;
+use crate::states::BridgeHandler;
+use crate::utils::realloc_program_account;
+
+// Reallocs a bridge handler created before fields were appended to BridgeHandler. The
+// handler is loaded manually because the old layout doesn't deserialize.
+#[derive(Accounts)]
+pub struct MigrateBridgeHandler<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: owner, discriminator, seeds and manager are checked after the realloc
+    bridge_handler: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl MigrateBridgeHandler<'_> {
+    pub fn migrate_bridge_handler(&mut self) -> Result<()> {
+        realloc_program_account::<BridgeHandler>(
+            &self.bridge_handler,
+            &self.manager,
+            &self.system_program,
+            8 + BridgeHandler::INIT_SPACE,
+        )?;
+
+        let mut bridge_handler = {
+            let data = self.bridge_handler.try_borrow_data()?;
+            BridgeHandler::try_deserialize(&mut data.as_ref())
+                .map_err(|e| e.with_account_name("bridge_handler"))?
+        };
+        let expected_bridge_handler = Pubkey::create_program_address(
+            &[
+                b"bridge_handler",
+                bridge_handler.init_nonce.to_be_bytes().as_ref(),
+                &[bridge_handler.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| BridgeHandlerError::InvalidAccountLayout)?;
+        require_keys_eq!(
+            self.bridge_handler.key(),
+            expected_bridge_handler,
+            BridgeHandlerError::InvalidAccountLayout
+        );
+        require_keys_eq!(
+            self.manager.key(),
+            bridge_handler.manager,
+            BridgeHandlerError::Unauthorized
+        );
+
+        // handlers from before the token admin existed hand the role to the manager
+        if bridge_handler.token_admin == Pubkey::default() {
+            bridge_handler.token_admin = bridge_handler.manager;
+            let mut data = self.bridge_handler.try_borrow_mut_data()?;
+            bridge_handler.try_serialize(&mut data.as_mut())?;
+        }
+
+        Ok(())
+    }
+}
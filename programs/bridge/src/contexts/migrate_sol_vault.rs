@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Moves the SOL that deposits credited to the bridge handler before the SOL vault existed
+// into the vault, so deliveries and refunds can pay it out.
+#[derive(Accounts)]
+pub struct MigrateSolVault<'info> {
+    manager: Signer<'info>,
+    #[account(
+        mut,
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
+}
+
+impl MigrateSolVault<'_> {
+    pub fn migrate_sol_vault(&mut self) -> Result<()> {
+        let bridge_handler = self.bridge_handler.to_account_info();
+        let lamports = bridge_handler
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(bridge_handler.data_len()));
+
+        msg!("moving {} lamports into the sol vault", lamports);
+
+        bridge_handler.sub_lamports(lamports)?;
+        self.sol_vault.add_lamports(lamports)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::states::{Chain, TokenInfo, TokenStatus};
+use crate::utils::realloc_program_account;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Converts a TokenInfo created before the listing status, decimals, limits and migrations
+// were added. The old layout ends with a `pause` flag where `status` is now.
+#[derive(Accounts)]
+pub struct MigrateTokenInfo<'info> {
+    #[account(mut)]
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    /// CHECK: still in the old layout, converted manually
+    token_info: UncheckedAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl MigrateTokenInfo<'_> {
+    pub fn migrate_token_info(&mut self, counterpart_decimal: u8) -> Result<()> {
+        require!(
+            self.token_info.data_len() == TokenInfo::LEGACY_LEN,
+            BridgeHandlerError::InvalidAccountLayout
+        );
+        let paused = self.token_info.try_borrow_data()?[TokenInfo::LEGACY_LEN - 1] != 0;
+
+        realloc_program_account::<TokenInfo>(
+            &self.token_info,
+            &self.token_admin,
+            &self.system_program,
+            8 + TokenInfo::INIT_SPACE,
+        )?;
+
+        // the appended fields are zeroed, so the old account reads as a Proposed token
+        let mut token_info = {
+            let data = self.token_info.try_borrow_data()?;
+            TokenInfo::try_deserialize(&mut data.as_ref())
+                .map_err(|e| e.with_account_name("token_info"))?
+        };
+        token_info.status = if paused {
+            TokenStatus::Paused
+        } else {
+            TokenStatus::Active
+        };
+        match self.bridge_handler.chain {
+            Chain::Solana => {
+                token_info.solana_decimals = self.mint.decimals;
+                token_info.solayer_decimals = counterpart_decimal;
+            }
+            Chain::Solayer => {
+                token_info.solana_decimals = counterpart_decimal;
+                token_info.solayer_decimals = self.mint.decimals;
+            }
+        }
+
+        msg!(
+            "migrated token info of {:?}, paused: {}",
+            self.mint.key(),
+            paused
+        );
+        let mut data = self.token_info.try_borrow_mut_data()?;
+        token_info.try_serialize(&mut data.as_mut())
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::constants::MAX_MAPPING_MIGRATIONS;
+use crate::states::{Chain, MappingMigration, TokenInfo, TokenStatus};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct MigrateTokenMapping<'info> {
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl MigrateTokenMapping<'_> {
+    pub fn migrate_token_mapping(
+        &mut self,
+        new_counterpart_mint: Pubkey,
+        new_counterpart_decimal: u8,
+    ) -> Result<()> {
+        // the token has to be paused while the mapping changes, transfers already in flight
+        // from the old counterpart are still delivered with the decimals kept in migrations
+        require!(
+            self.token_info.status == TokenStatus::Paused,
+            BridgeHandlerError::InvalidTokenStatus
+        );
+        require!(
+            self.token_info.migrations.len() < MAX_MAPPING_MIGRATIONS,
+            BridgeHandlerError::TooManyMappingMigrations
+        );
+
+        let chain = self.bridge_handler.chain;
+        let old_counterpart_mint = self.token_info.counterpart_mint(chain);
+        require!(
+            new_counterpart_mint != Pubkey::default()
+                && new_counterpart_mint != old_counterpart_mint,
+            BridgeHandlerError::InvalidCounterpartMint
+        );
+
+        let counterpart_chain = match chain {
+            Chain::Solana => Chain::Solayer,
+            Chain::Solayer => Chain::Solana,
+        };
+        let old_counterpart_decimals = self.token_info.decimals(counterpart_chain);
+
+        msg!(
+            "migrating mapping of {:?} from {:?} to {:?}, counterpart decimals: {} -> {}",
+            self.mint.key(),
+            old_counterpart_mint,
+            new_counterpart_mint,
+            old_counterpart_decimals,
+            new_counterpart_decimal
+        );
+
+        match chain {
+            Chain::Solana => {
+                self.token_info.solayer_mint = new_counterpart_mint;
+                self.token_info.solayer_decimals = new_counterpart_decimal;
+            }
+            Chain::Solayer => {
+                self.token_info.solana_mint = new_counterpart_mint;
+                self.token_info.solana_decimals = new_counterpart_decimal;
+            }
+        }
+
+        self.token_info.migrations.push(MappingMigration {
+            old_counterpart_mint,
+            old_counterpart_decimals,
+            new_counterpart_mint,
+            new_counterpart_decimals: new_counterpart_decimal,
+            migrated_by: self.token_admin.key(),
+            migrated_at: Clock::get()?.unix_timestamp as u64,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::states::VerifiedSignatures;
+use crate::utils::realloc_program_account;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Converts guardian signatures verified before their payer was recorded. The operator
+// funded those accounts, so it tops up the rent and is recorded as the payer.
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct MigrateVerifiedSignatures<'info> {
+    #[account(mut)]
+    operator: Signer<'info>,
+    #[account(
+        has_one = operator @ BridgeHandlerError::InvalidOperator,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
+        bump
+    )]
+    /// CHECK: still in the old layout, converted manually
+    verified_signatures: UncheckedAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl MigrateVerifiedSignatures<'_> {
+    pub fn migrate_verified_signatures(&mut self, msg_hash: [u8; 32]) -> Result<()> {
+        require!(
+            self.verified_signatures.data_len() == VerifiedSignatures::LEGACY_LEN,
+            BridgeHandlerError::InvalidAccountLayout
+        );
+
+        realloc_program_account::<VerifiedSignatures>(
+            &self.verified_signatures,
+            &self.operator,
+            &self.system_program,
+            8 + VerifiedSignatures::INIT_SPACE,
+        )?;
+
+        // the appended payer is zeroed
+        let mut verified_signatures = {
+            let data = self.verified_signatures.try_borrow_data()?;
+            VerifiedSignatures::try_deserialize(&mut data.as_ref())
+                .map_err(|e| e.with_account_name("verified_signatures"))?
+        };
+        verified_signatures.payer = self.operator.key();
+
+        msg!("migrated verified signatures of {:?}", msg_hash);
+        let mut data = self.verified_signatures.try_borrow_mut_data()?;
+        verified_signatures.try_serialize(&mut data.as_mut())
+    }
+}
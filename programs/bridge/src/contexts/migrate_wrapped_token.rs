@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    burn, mint_to, Burn, Mint, MintTo, Token2022, TokenAccount, TokenInterface,
+};
+
+use crate::states::{MintMigration, TokenInfo};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct MigrateWrappedToken<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        has_one = old_mint,
+        has_one = new_mint,
+        seeds = [b"mint_migration", bridge_handler.key().as_ref(), old_mint.key().as_ref()],
+        bump = mint_migration.bump
+    )]
+    mint_migration: Box<Account<'info, MintMigration>>,
+    #[account(
+        mut,
+        mint::token_program = old_token_program
+    )]
+    old_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = old_mint,
+        token::token_program = old_token_program,
+    )]
+    signer_old_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), old_mint.key().as_ref()],
+        bump = old_token_info.bump
+    )]
+    old_token_info: Box<Account<'info, TokenInfo>>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    new_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::authority = signer,
+        associated_token::mint = new_mint,
+        associated_token::token_program = token_program
+    )]
+    signer_new_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), new_mint.key().as_ref()],
+        bump = new_token_info.bump
+    )]
+    new_token_info: Box<Account<'info, TokenInfo>>,
+    old_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token2022>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl MigrateWrappedToken<'_> {
+    pub fn migrate_wrapped_token(&mut self, amount: u64) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(
+            amount > 0 && self.signer_old_vault.amount >= amount,
+            BridgeHandlerError::InsufficientAmount
+        );
+
+        msg!(
+            "migrating {:?} token of {:?} to {:?}",
+            amount,
+            self.old_mint.key(),
+            self.new_mint.key()
+        );
+
+        let ctx = CpiContext::new(
+            self.old_token_program.to_account_info(),
+            Burn {
+                mint: self.old_mint.to_account_info(),
+                from: self.signer_old_vault.to_account_info(),
+                authority: self.signer.to_account_info(),
+            },
+        );
+        burn(ctx, amount)?;
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.new_mint.to_account_info(),
+                to: self.signer_new_vault.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        mint_to(ctx, amount)?;
+
+        self.mint_migration.migrated_supply = self
+            .mint_migration
+            .migrated_supply
+            .checked_add(amount)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        self.old_token_info.migrated_supply = self
+            .old_token_info
+            .migrated_supply
+            .checked_add(amount)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        self.new_token_info.migrated_supply = self
+            .new_token_info
+            .migrated_supply
+            .checked_add(amount)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::{
+        create_master_edition_v3, create_metadata_accounts_v3,
+        mpl_token_metadata::types::{Collection, Creator, DataV2},
+        verify_sized_collection_item, CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+        VerifySizedCollectionItem,
+    },
+    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+};
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProof, ChainConfig, DeliveryBitmap, GuardianInfo, NftInfo,
+    NftMetadata, VerifiedSignatures,
+};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32], sequence: u64, source_tx_id: [u8; 64], native_mint: Pubkey, sender: [u8; 32])]
+pub struct MintNftTargetChain<'info> {
+    // the operator, or anyone claiming a delivery the guardians have attested
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + NftInfo::INIT_SPACE,
+        seeds = [b"nft_info", bridge_handler.key().as_ref(), native_mint.as_ref()],
+        bump
+    )]
+    nft_info: Box<Account<'info, NftInfo>>,
+    #[account(
+        init,
+        payer = payer,
+        mint::authority = bridge_handler,
+        mint::freeze_authority = bridge_handler,
+        mint::decimals = 0,
+        seeds = [b"nft_mint", bridge_handler.key().as_ref(), native_mint.as_ref(), nft_info.generation.to_be_bytes().as_ref()],
+        bump
+    )]
+    mint: Box<Account<'info, Mint>>,
+    /// CHECKED: checks will be performed agsint signature with hash
+    recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::authority = recipient,
+        associated_token::mint = mint,
+    )]
+    recipient_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&mint.key()).0
+    )]
+    /// CHECK: This is the metadata account
+    metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&mint.key()).0
+    )]
+    /// CHECK: This is the master edition account
+    master_edition: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"nft_collection", bridge_handler.key().as_ref()],
+        bump
+    )]
+    collection_mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+    )]
+    /// CHECK: This is the metadata account of the bridge collection
+    collection_metadata: UncheckedAccount<'info>,
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+    )]
+    /// CHECK: This is the master edition account of the bridge collection
+    collection_master_edition: UncheckedAccount<'info>,
+    // optional per-transfer receipt, replays are rejected by the delivery bitmap
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BridgeProof::INIT_SPACE,
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), sequence.to_be_bytes().as_ref()],
+        bump
+    )]
+    bridge_proof: Option<Box<Account<'info, BridgeProof>>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DeliveryBitmap::INIT_SPACE,
+        seeds = [b"delivery_bitmap", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), DeliveryBitmap::page(sequence).to_be_bytes().as_ref()],
+        bump
+    )]
+    delivery_bitmap: Box<Account<'info, DeliveryBitmap>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    token_metadata_program: Program<'info, Metadata>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+}
+
+impl MintNftTargetChain<'_> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_target_chain(
+        &mut self,
+        bumps: MintNftTargetChainBumps,
+        msg_hash: [u8; 32],
+        sequence: u64,
+        source_tx_id: [u8; 64],
+        native_mint: Pubkey,
+        sender: [u8; 32],
+        nft_metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
+
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&sender)?;
+        let message = BridgeMessage {
+            source_chain_id: self.chain_config.chain_id,
+            source_emitter: self.chain_config.emitter,
+            destination_chain_id: self.bridge_handler.chain.chain_id(),
+            destination_emitter: self.bridge_handler.key().to_bytes(),
+            sender,
+            recipient: self.recipient.key().to_bytes(),
+            sequence,
+            deadline,
+            payload: BridgePayload::Nft {
+                native_mint,
+                metadata: nft_metadata.clone(),
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
+        require!(
+            message == msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+
+        require!(
+            self.nft_info.wrapped_mint == Pubkey::default(),
+            BridgeHandlerError::NftAlreadyBridged
+        );
+        // an NFT wrapped before keeps the chain it was first sent from
+        require!(
+            self.nft_info.native_chain_id == 0
+                || self.nft_info.native_chain_id == self.chain_config.chain_id,
+            BridgeHandlerError::InvalidCounterpartChain
+        );
+        self.nft_info.bump = bumps.nft_info;
+        self.nft_info.native_mint = native_mint;
+        self.nft_info.native_chain_id = self.chain_config.chain_id;
+        self.nft_info.wrapped_mint = self.mint.key();
+        self.nft_info.native_collection = nft_metadata.collection;
+
+        msg!(
+            "mint wrapped nft {:?} of {:?} to recipient, generation: {}",
+            self.mint.key(),
+            native_mint,
+            self.nft_info.generation
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.mint.to_account_info(),
+                to: self.recipient_vault.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        mint_to(ctx, 1)?;
+
+        let creators = nft_metadata
+            .creators
+            .iter()
+            .map(|creator| Creator {
+                address: creator.address,
+                verified: false,
+                share: creator.share,
+            })
+            .collect::<Vec<Creator>>();
+
+        let nft_data = DataV2 {
+            name: nft_metadata.name,
+            symbol: nft_metadata.symbol,
+            uri: nft_metadata.uri,
+            seller_fee_basis_points: 0,
+            creators: if creators.is_empty() {
+                None
+            } else {
+                Some(creators)
+            },
+            collection: Some(Collection {
+                verified: false,
+                key: self.collection_mint.key(),
+            }),
+            uses: None,
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: self.metadata.to_account_info(),
+                mint: self.mint.to_account_info(),
+                mint_authority: self.bridge_handler.to_account_info(),
+                payer: self.payer.to_account_info(),
+                update_authority: self.bridge_handler.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        create_metadata_accounts_v3(ctx, nft_data, true, true, None)?;
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: self.master_edition.to_account_info(),
+                mint: self.mint.to_account_info(),
+                update_authority: self.bridge_handler.to_account_info(),
+                mint_authority: self.bridge_handler.to_account_info(),
+                payer: self.payer.to_account_info(),
+                metadata: self.metadata.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                rent: self.rent.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        create_master_edition_v3(ctx, Some(0))?;
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_metadata_program.to_account_info(),
+            VerifySizedCollectionItem {
+                payer: self.payer.to_account_info(),
+                metadata: self.metadata.to_account_info(),
+                collection_authority: self.bridge_handler.to_account_info(),
+                collection_mint: self.collection_mint.to_account_info(),
+                collection_metadata: self.collection_metadata.to_account_info(),
+                collection_master_edition: self.collection_master_edition.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        verify_sized_collection_item(ctx, None)?;
+
+        let recipient = self.recipient.key();
+        let payer = self.payer.key();
+        let receipt = self
+            .bridge_proof
+            .as_deref_mut()
+            .map(|bridge_proof| &mut **bridge_proof)
+            .zip(bumps.bridge_proof);
+        self.delivery_bitmap.record_delivery(
+            bumps.delivery_bitmap,
+            sequence,
+            receipt,
+            message,
+            source_tx_id,
+            recipient,
+            payer,
+        )?;
+
+        Ok(())
+    }
+}
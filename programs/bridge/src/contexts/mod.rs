@@ -48,3 +48,102 @@ pub use update_operator::*;
 
 pub mod update_manager;
 pub use update_manager::*;
+
+pub mod quote_bridge;
+pub use quote_bridge::*;
+
+pub mod set_fee_exemption;
+pub use set_fee_exemption::*;
+
+pub mod remove_fee_exemption;
+pub use remove_fee_exemption::*;
+
+pub mod update_strict_mint_extensions;
+pub use update_strict_mint_extensions::*;
+
+pub mod add_token_2022;
+pub use add_token_2022::*;
+
+pub mod update_token_admin;
+pub use update_token_admin::*;
+
+pub mod update_token_metadata;
+pub use update_token_metadata::*;
+
+pub mod sync_token_metadata;
+pub use sync_token_metadata::*;
+
+pub mod attest_token;
+pub use attest_token::*;
+
+pub mod register_token;
+pub use register_token::*;
+
+pub mod propose_token;
+pub use propose_token::*;
+
+pub mod activate_token;
+pub use activate_token::*;
+
+pub mod delist_token;
+pub use delist_token::*;
+
+pub mod migrate_token_mapping;
+pub use migrate_token_mapping::*;
+
+pub mod init_nft_collection;
+pub use init_nft_collection::*;
+
+pub mod lock_nft_source_chain;
+pub use lock_nft_source_chain::*;
+
+pub mod burn_nft_source_chain;
+pub use burn_nft_source_chain::*;
+
+pub mod unlock_nft_target_chain;
+pub use unlock_nft_target_chain::*;
+
+pub mod mint_nft_target_chain;
+pub use mint_nft_target_chain::*;
+
+pub mod init_liquidity_pool;
+pub use init_liquidity_pool::*;
+
+pub mod manage_liquidity;
+pub use manage_liquidity::*;
+
+pub mod register_mint_migration;
+pub use register_mint_migration::*;
+
+pub mod migrate_wrapped_token;
+pub use migrate_wrapped_token::*;
+pub mod update_token_transfer_limits;
+pub use update_token_transfer_limits::*;
+pub mod add_chain_config;
+pub use add_chain_config::*;
+pub mod update_chain_config;
+pub use update_chain_config::*;
+pub mod pause_chain;
+pub use pause_chain::*;
+pub mod close_bridge_proof_source_chain;
+pub use close_bridge_proof_source_chain::*;
+pub mod close_bridge_proof;
+pub use close_bridge_proof::*;
+pub mod refund_bridge_asset_source_chain;
+pub use refund_bridge_asset_source_chain::*;
+pub mod migrate_bridge_handler;
+pub use migrate_bridge_handler::*;
+pub mod migrate_token_info;
+pub use migrate_token_info::*;
+pub mod refund_bridge_asset_source_chain_sol;
+pub use refund_bridge_asset_source_chain_sol::*;
+pub mod refund_nft_source_chain;
+pub use refund_nft_source_chain::*;
+pub mod quote_delivery;
+pub use quote_delivery::*;
+pub mod migrate_sol_vault;
+pub use migrate_sol_vault::*;
+pub mod close_legacy_bridge_proof;
+pub use close_legacy_bridge_proof::*;
+pub mod migrate_verified_signatures;
+pub use migrate_verified_signatures::*;
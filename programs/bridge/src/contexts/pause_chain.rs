@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::states::ChainConfig;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct PauseChain<'info> {
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+}
+
+impl PauseChain<'_> {
+    pub fn pause_chain(&mut self) -> Result<()> {
+        self.chain_config.pause = true;
+        Ok(())
+    }
+
+    pub fn unpause_chain(&mut self) -> Result<()> {
+        self.chain_config.pause = false;
+        Ok(())
+    }
+}
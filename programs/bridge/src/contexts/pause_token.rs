@@ -3,7 +3,7 @@ use anchor_spl::token_interface::{Mint, TokenInterface};
 
 use crate::{
     errors::BridgeHandlerError,
-    states::{BridgeHandler, TokenInfo},
+    states::{BridgeHandler, TokenInfo, TokenStatus},
 };
 
 #[derive(Accounts)]
@@ -20,6 +20,7 @@ pub struct PauseToken<'info> {
     )]
     mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
+        mut,
         seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
         bump = token_info.bump
     )]
@@ -29,12 +30,20 @@ pub struct PauseToken<'info> {
 
 impl PauseToken<'_> {
     pub fn pause_token(&mut self) -> Result<()> {
-        self.token_info.pause = true;
+        require!(
+            self.token_info.status == TokenStatus::Active,
+            BridgeHandlerError::InvalidTokenStatus
+        );
+        self.token_info.status = TokenStatus::Paused;
         Ok(())
     }
 
     pub fn unpause_token(&mut self) -> Result<()> {
-        self.token_info.pause = false;
+        require!(
+            self.token_info.status == TokenStatus::Paused,
+            BridgeHandlerError::InvalidTokenStatus
+        );
+        self.token_info.status = TokenStatus::Active;
         Ok(())
     }
 }
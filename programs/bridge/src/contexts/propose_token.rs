@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::states::{Chain, TokenInfo, TokenStatus};
+use crate::utils::check_mint_extensions;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct ProposeToken<'info> {
+    #[account(mut)]
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = token_admin,
+        space = 8 + TokenInfo::INIT_SPACE,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl ProposeToken<'_> {
+    pub fn propose_token(&mut self, token_info_bump: u8, target_decimal: u8) -> Result<()> {
+        let target_mint = TokenInfo::wrapped_mint(&self.bridge_handler.key(), &self.mint.key());
+
+        self.propose(token_info_bump, target_mint, target_decimal, false)
+    }
+
+    // pool mode tokens are issued natively on both chains, so both sides lock and unlock
+    // from vaults and the counterpart mint has to be named by the token admin
+    pub fn propose_pool_token(
+        &mut self,
+        token_info_bump: u8,
+        target_mint: Pubkey,
+        target_decimal: u8,
+    ) -> Result<()> {
+        require!(
+            target_mint != Pubkey::default(),
+            BridgeHandlerError::InvalidCounterpartMint
+        );
+
+        self.propose(token_info_bump, target_mint, target_decimal, true)
+    }
+
+    fn propose(
+        &mut self,
+        token_info_bump: u8,
+        target_mint: Pubkey,
+        target_decimal: u8,
+        pool_mode: bool,
+    ) -> Result<()> {
+        require_keys_neq!(
+            self.mint.key(),
+            native_mint::ID,
+            BridgeHandlerError::WsolNotSupported
+        );
+        if self.bridge_handler.strict_mint_extensions {
+            check_mint_extensions(&self.mint.to_account_info())?;
+        }
+
+        TokenInfo::check_counterpart_decimals(self.mint.decimals, target_decimal, !pool_mode)?;
+
+        // attestations are permissionless and may have listed the mint already, the token
+        // admin can still replace the mapping until the token is activated
+        if self.token_info.is_solana_native_token || self.token_info.is_solayer_native_token {
+            require!(
+                self.token_info.status == TokenStatus::Proposed,
+                BridgeHandlerError::TokenAlreadyExists
+            );
+            // register_token may have created the wrapped mint with the attested decimals
+            let chain = self.bridge_handler.chain;
+            if self.token_info.counterpart_mint(chain) == target_mint {
+                require!(
+                    target_decimal == self.token_info.counterpart_decimals(chain, &target_mint)?,
+                    BridgeHandlerError::InvalidTargetDecimals
+                );
+            }
+        }
+
+        let chain = self.bridge_handler.chain;
+        let mint = self.mint.key();
+
+        let (solana_mint, solayer_mint, solana_decimals, solayer_decimals) =
+            if chain == Chain::Solana {
+                (mint, target_mint, self.mint.decimals, target_decimal)
+            } else {
+                (target_mint, mint, target_decimal, self.mint.decimals)
+            };
+
+        msg!(
+            "proposing token with solana_mint: {:?}, solayer_mint: {:?}, solana_decimals: {}, solayer_decimals: {}, pool_mode: {}",
+            solana_mint,
+            solayer_mint,
+            solana_decimals,
+            solayer_decimals,
+            pool_mode
+        );
+
+        self.token_info.init(
+            token_info_bump,
+            solana_mint,
+            solayer_mint,
+            pool_mode || chain == Chain::Solana,
+            pool_mode || chain == Chain::Solayer,
+            solana_decimals,
+            solayer_decimals,
+            TokenStatus::Proposed,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::Mint;
+
+use crate::constants::{MAX_ADDITIONAL_SOL_GAS, MIN_SOL_BRIDGE_AMOUNT, SOL_MINT_PUBKEY};
+use crate::states::{Chain, ChainConfig, FeeExemption, TokenInfo};
+use crate::utils::{check_mint_extensions, get_transfer_fee};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct BridgeQuote {
+    // all fees in form of lamports
+    pub bridge_asset_fee: u64,
+    pub additional_sol_gas: u64,
+    pub total_fee: u64, // bridge_asset_fee and additional_sol_gas
+    // withheld by Token-2022 TransferFee mints, in the smallest unit of the bridged asset
+    pub transfer_fee: u64,
+    // left with the sender as it can't be represented in the message precision
+    pub dust: u64,
+    // amount carried by the bridge message, quote_delivery takes it on the target chain
+    pub message_amount: u64,
+    // amount the recipient receives, in the smallest unit of the bridged asset
+    pub net_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteBridge<'info> {
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    /// CHECK: either a token mint, or SOL_MINT_PUBKEY or the wSOL mint for the native SOL path
+    mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    /// CHECK: loaded manually so that unlisted mints are rejected with TokenNotExists
+    token_info: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"fee_exemption", bridge_handler.key().as_ref(), fee_exemption.exempt_account.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+}
+
+impl QuoteBridge<'_> {
+    pub fn quote_bridge(&self, amount: u64, additional_sol_gas: u64) -> Result<BridgeQuote> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            additional_sol_gas <= MAX_ADDITIONAL_SOL_GAS,
+            BridgeHandlerError::TooMuchAdditionalSolGas
+        );
+        if additional_sol_gas > 0 {
+            require!(
+                additional_sol_gas >= MIN_SOL_BRIDGE_AMOUNT,
+                BridgeHandlerError::TooLittleAdditionalSolGas
+            );
+        }
+
+        let chain = self.bridge_handler.chain;
+        // the quote does not know who will sign, so a provided exemption is applied as is
+        let bridge_asset_fee = match &self.fee_exemption {
+            Some(fee_exemption) => {
+                fee_exemption.apply_discount(self.chain_config.fee_info.bridge_asset_fee)?
+            }
+            None => self.chain_config.fee_info.bridge_asset_fee,
+        };
+        let mut transfer_fee = 0;
+        let mut dust = 0;
+        let mut message_amount = amount;
+        let mut net_amount = amount;
+
+        if self.mint.key() == SOL_MINT_PUBKEY || self.mint.key() == native_mint::ID {
+            require!(
+                amount >= MIN_SOL_BRIDGE_AMOUNT,
+                BridgeHandlerError::TooLittleSolBridgeAmount
+            );
+            require!(
+                additional_sol_gas == 0,
+                BridgeHandlerError::InvalidAdditionalSolGas
+            );
+        } else {
+            load_mint(&self.mint)?;
+            let token_info = load_token_info(&self.token_info)?;
+
+            if self.bridge_handler.strict_mint_extensions {
+                check_mint_extensions(&self.mint)?;
+            }
+
+            token_info.check_bridgeable()?;
+            TokenInfo::check_counterpart_chain(chain, self.chain_config.chain_id)?;
+            let is_locked_on_current_chain = (chain == Chain::Solana
+                && token_info.is_solana_native_token)
+                || (chain == Chain::Solayer && token_info.is_solayer_native_token);
+
+            // dust below the message precision is never taken from the sender
+            (_, dust) = token_info.normalize_amount(chain, amount)?;
+            let transfer_amount = amount
+                .checked_sub(dust)
+                .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+
+            if is_locked_on_current_chain {
+                transfer_fee = get_transfer_fee(&self.mint, transfer_amount)?;
+            }
+
+            let received_amount = transfer_amount
+                .checked_sub(transfer_fee)
+                .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+            (message_amount, _) = token_info.normalize_amount(chain, received_amount)?;
+            token_info.check_transfer_amount(message_amount)?;
+            net_amount = token_info.denormalize_amount(chain, message_amount)?;
+        }
+
+        // the fee and the additional gas are both paid in lamports on top of the amount
+        let total_fee = bridge_asset_fee
+            .checked_add(additional_sol_gas)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+
+        Ok(BridgeQuote {
+            bridge_asset_fee,
+            additional_sol_gas,
+            total_fee,
+            transfer_fee,
+            dust,
+            message_amount,
+            net_amount,
+        })
+    }
+}
+
+// quotes are read only and take the mint and token info unchecked, so that unsupported
+// mints are rejected with a bridge error instead of failing account validation
+pub(crate) fn load_mint(mint: &UncheckedAccount) -> Result<Mint> {
+    let owner_program = mint.owner;
+    if owner_program != &anchor_spl::token::ID && owner_program != &anchor_spl::token_2022::ID {
+        return Err(anchor_lang::error::Error::from(
+            anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram,
+        )
+        .with_account_name("mint"));
+    }
+
+    let data = mint.try_borrow_data()?;
+    Mint::try_deserialize(&mut data.as_ref()).map_err(|e| e.with_account_name("mint"))
+}
+
+pub(crate) fn load_token_info(token_info: &UncheckedAccount) -> Result<TokenInfo> {
+    if token_info.owner != &crate::ID {
+        return Err(BridgeHandlerError::TokenNotExists.into());
+    }
+
+    let data = token_info.try_borrow_data()?;
+    TokenInfo::try_deserialize(&mut data.as_ref()).map_err(|e| e.with_account_name("token_info"))
+}
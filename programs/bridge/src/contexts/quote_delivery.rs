@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+
+use crate::constants::{
+    get_sol_dollar_value, get_whitelisted_token_price, is_token_whitelisted, MIN_SOL_BRIDGE_AMOUNT,
+    SOL_MINT_PUBKEY,
+};
+use crate::contexts::{load_mint, load_token_info};
+use crate::states::{Chain, ChainConfig, TokenInfo};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DeliveryQuote {
+    // amount the recipient receives, in the smallest unit of the delivered asset
+    pub amount: u64,
+    // the instant cap is only enforced on Solana, the delay counts from the guardian
+    // signatures being verified
+    pub exceeds_instant_cap: bool,
+    pub expected_delay_seconds: u64,
+}
+
+// Quotes the delivery of a message_amount from quote_bridge on the target chain, where the
+// instant bridge cap of the source chain config is enforced.
+#[derive(Accounts)]
+pub struct QuoteDelivery<'info> {
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    /// CHECK: either a token mint, or SOL_MINT_PUBKEY or the wSOL mint for the native SOL path
+    mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    /// CHECK: loaded manually so that unlisted mints are rejected with TokenNotExists
+    token_info: UncheckedAccount<'info>,
+}
+
+impl QuoteDelivery<'_> {
+    pub fn quote_delivery(
+        &self,
+        source_mint: Pubkey,
+        message_amount: u64,
+    ) -> Result<DeliveryQuote> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+
+        let chain = self.bridge_handler.chain;
+
+        let (amount, dollar_value) = if self.mint.key() == SOL_MINT_PUBKEY
+            || self.mint.key() == native_mint::ID
+        {
+            require!(
+                message_amount >= MIN_SOL_BRIDGE_AMOUNT,
+                BridgeHandlerError::TooLittleSolBridgeAmount
+            );
+            (message_amount, Some(get_sol_dollar_value(message_amount)))
+        } else {
+            let mint = load_mint(&self.mint)?;
+            let token_info = load_token_info(&self.token_info)?;
+
+            TokenInfo::check_counterpart_chain(chain, self.chain_config.chain_id)?;
+            token_info.check_deliverable(Clock::get()?.unix_timestamp as u64)?;
+            token_info.check_transfer_amount(message_amount)?;
+            let amount = token_info.denormalize_amount_for(chain, &source_mint, message_amount)?;
+
+            let dollar_value = if is_token_whitelisted(self.mint.key()) {
+                Some(
+                    get_whitelisted_token_price(self.mint.key(), amount, mint.decimals)
+                        .ok_or(BridgeHandlerError::TokenNotExists)?,
+                )
+            } else {
+                None
+            };
+            (amount, dollar_value)
+        };
+
+        let expected_delay_seconds = match dollar_value {
+            Some(dollar_value) if chain == Chain::Solana => {
+                self.chain_config
+                    .instant_cap_delay(dollar_value as u64, Clock::get()?.epoch, 0)
+            }
+            _ => 0,
+        };
+
+        Ok(DeliveryQuote {
+            amount,
+            exceeds_instant_cap: expected_delay_seconds > 0,
+            expected_delay_seconds,
+        })
+    }
+}
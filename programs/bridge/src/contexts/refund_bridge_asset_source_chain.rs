@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, GuardianInfo, TokenInfo,
+    VerifiedSignatures,
+};
+use crate::utils::{transfer_checked_with_hook, transfer_sol_from_vault};
+use crate::{
+    errors::BridgeHandlerError,
+    states::{BridgeHandler, Chain},
+};
+
+// The below precompile is used to mint SOL on Solayer only
+#[allow(dead_code)]
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_mint_native_sol(amount: u64, account_idx: u64) -> u64;
+}
+
+// Returns a token transfer that can no longer be delivered to its original sender, once its
+// deadline passed and the guardians attest the refund. Locked tokens leave the vault,
+// burned tokens are re-minted. The proof is closed, so a transfer is refunded only once.
+#[derive(Accounts)]
+#[instruction(sequence: u64)]
+pub struct RefundBridgeAssetSourceChain<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    signer_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    // only needed when the transfer locked the tokens
+    #[account(
+        mut,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = mint,
+        associated_token::token_program = token_program
+    )]
+    bridge_handler_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sequence.to_be_bytes().as_ref()],
+        bump = bridge_proof.bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    #[account(
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    // guardians attesting that the message will not be delivered on the target chain
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), BridgeMessage::refund_hash(&bridge_proof.msg_hash).as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    // SOL deposits are held here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> RefundBridgeAssetSourceChain<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn refund_bridge_asset_source_chain(
+        &mut self,
+        bumps: RefundBridgeAssetSourceChainBumps,
+        sequence: u64,
+        recipient: [u8; 32],
+        target_mint: Pubkey,
+        amount: u64,
+        additional_sol_gas: u64,
+        deadline: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        BridgeMessage::check_refundable(deadline, Clock::get()?.unix_timestamp as u64)?;
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        // the target mint comes from the caller, the mapping may have changed since
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence,
+            deadline,
+            payload: BridgePayload::Token {
+                source_mint: self.mint.key(),
+                target_mint,
+                amount,
+                additional_sol_gas,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
+        require!(
+            message == self.bridge_proof.msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+
+        let chain = self.bridge_handler.chain;
+        let refund_amount = self
+            .token_info
+            .denormalize_amount_for(chain, &target_mint, amount)?;
+        msg!(
+            "refunding {:?} token of {:?} to {:?}",
+            refund_amount,
+            self.mint.key(),
+            self.signer.key()
+        );
+
+        if (chain == Chain::Solana && self.token_info.is_solana_native_token)
+            || (chain == Chain::Solayer && self.token_info.is_solayer_native_token)
+        {
+            self.unlock_token(refund_amount, remaining_accounts)?;
+        } else {
+            self.mint_token(refund_amount)?;
+        }
+
+        if additional_sol_gas > 0 {
+            if chain == Chain::Solayer {
+                self.mint_sol_to_signer(additional_sol_gas)?;
+            } else {
+                self.transfer_sol_to_signer(bumps.sol_vault, additional_sol_gas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn unlock_token(
+        &mut self,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let Some(bridge_handler_vault) = &self.bridge_handler_vault else {
+            return Err(BridgeHandlerError::MissingBridgeHandlerVault.into());
+        };
+        require!(
+            bridge_handler_vault.amount >= amount,
+            BridgeHandlerError::InsufficientFunds
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        transfer_checked_with_hook(
+            &self.token_program.to_account_info(),
+            bridge_handler_vault.to_account_info(),
+            self.mint.to_account_info(),
+            self.signer_vault.to_account_info(),
+            self.bridge_handler.to_account_info(),
+            remaining_accounts,
+            amount,
+            self.mint.decimals,
+            &signer_seeds[..],
+        )
+    }
+
+    fn mint_token(&mut self, amount: u64) -> Result<()> {
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.mint.to_account_info(),
+                to: self.signer_vault.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+
+        mint_to(ctx, amount)
+    }
+
+    #[cfg(feature = "solayer")]
+    fn mint_sol_to_signer(&mut self, lamports: u64) -> Result<()> {
+        #[cfg(target_os = "solana")]
+        let result = unsafe { sol_mint_native_sol(lamports, 0) };
+        #[cfg(not(target_os = "solana"))]
+        let result = 0;
+
+        match result {
+            0 => {
+                msg!(
+                    "SUCCESS: Minted {} lamports to {}",
+                    lamports,
+                    self.signer.key()
+                );
+                self.signer.add_lamports(lamports)?;
+            }
+            _ => {
+                msg!(
+                    "ERROR: Failed to mint {} lamports to {}",
+                    lamports,
+                    self.signer.key()
+                );
+                return Err(BridgeHandlerError::FailToMintSol.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "solayer"))]
+    fn mint_sol_to_signer(&mut self, _lamports: u64) -> Result<()> {
+        require!(false, BridgeHandlerError::InvalidOSForMintingSol);
+        Ok(())
+    }
+
+    fn transfer_sol_to_signer(&mut self, sol_vault_bump: u8, lamports: u64) -> Result<()> {
+        transfer_sol_from_vault(
+            &self.system_program.to_account_info(),
+            &self.sol_vault.to_account_info(),
+            &self.signer.to_account_info(),
+            &self.bridge_handler.key(),
+            sol_vault_bump,
+            lamports,
+        )
+    }
+}
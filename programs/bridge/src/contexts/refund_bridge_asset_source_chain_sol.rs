@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, GuardianInfo,
+    VerifiedSignatures,
+};
+use crate::utils::{instruction_account_index, transfer_sol_from_vault};
+use crate::{
+    errors::BridgeHandlerError,
+    states::{BridgeHandler, Chain},
+};
+
+// The below precompile is used to mint SOL on Solayer only
+#[allow(dead_code)]
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_mint_native_sol(amount: u64, account_idx: u64) -> u64;
+}
+
+// Returns a SOL transfer that can no longer be delivered to its original sender, the same
+// way refund_bridge_asset_source_chain does for tokens. The bridge fee is not refunded.
+#[derive(Accounts)]
+#[instruction(sequence: u64)]
+pub struct RefundBridgeAssetSourceChainSol<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sequence.to_be_bytes().as_ref()],
+        bump = bridge_proof.bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    // guardians attesting that the message will not be delivered on the target chain
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), BridgeMessage::refund_hash(&bridge_proof.msg_hash).as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    // SOL deposits are held here on Solana
+    #[account(
+        mut,
+        seeds = [b"sol_vault", bridge_handler.key().as_ref()],
+        bump
+    )]
+    sol_vault: SystemAccount<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl RefundBridgeAssetSourceChainSol<'_> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn refund_bridge_asset_source_chain_sol(
+        &mut self,
+        bumps: RefundBridgeAssetSourceChainSolBumps,
+        sequence: u64,
+        recipient: [u8; 32],
+        amount: u64,
+        deliver_as_wsol: bool,
+        deadline: u64,
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        BridgeMessage::check_refundable(deadline, Clock::get()?.unix_timestamp as u64)?;
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence,
+            deadline,
+            payload: BridgePayload::Sol {
+                amount,
+                deliver_as_wsol,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
+        require!(
+            message == self.bridge_proof.msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+
+        msg!("refunding {:?} sol to {:?}", amount, self.signer.key());
+
+        // deposits are held by the SOL vault on Solana and burned on Solayer
+        if self.bridge_handler.chain == Chain::Solayer {
+            let account_idx = instruction_account_index(self, self.signer.key)?;
+            self.mint_sol_to_signer(account_idx, amount)?;
+        } else {
+            self.transfer_sol_to_signer(bumps.sol_vault, amount)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "solayer")]
+    #[cfg_attr(not(target_os = "solana"), allow(unused_variables))]
+    fn mint_sol_to_signer(&mut self, account_idx: u64, lamports: u64) -> Result<()> {
+        #[cfg(target_os = "solana")]
+        let result = unsafe { sol_mint_native_sol(lamports, account_idx) };
+        #[cfg(not(target_os = "solana"))]
+        let result = 0;
+
+        match result {
+            0 => {
+                msg!(
+                    "SUCCESS: Minted {} lamports to {}",
+                    lamports,
+                    self.signer.key()
+                );
+                self.signer.add_lamports(lamports)?;
+            }
+            _ => {
+                msg!(
+                    "ERROR: Failed to mint {} lamports to {}",
+                    lamports,
+                    self.signer.key()
+                );
+                return Err(BridgeHandlerError::FailToMintSol.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "solayer"))]
+    fn mint_sol_to_signer(&mut self, _account_idx: u64, _lamports: u64) -> Result<()> {
+        require!(false, BridgeHandlerError::InvalidOSForMintingSol);
+        Ok(())
+    }
+
+    fn transfer_sol_to_signer(&mut self, sol_vault_bump: u8, lamports: u64) -> Result<()> {
+        transfer_sol_from_vault(
+            &self.system_program.to_account_info(),
+            &self.sol_vault.to_account_info(),
+            &self.signer.to_account_info(),
+            &self.bridge_handler.key(),
+            sol_vault_bump,
+            lamports,
+        )
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProofSourceChain, ChainConfig, GuardianInfo, NftMetadata,
+    VerifiedSignatures,
+};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+// Returns a native NFT locked by lock_nft_source_chain that can no longer be delivered, the
+// same way refund_bridge_asset_source_chain does for tokens. Wrapped NFTs sent back through
+// burn_nft_source_chain are burned together with their metadata and edition, so they have
+// no refund path: the native NFT stays locked on its native chain.
+#[derive(Accounts)]
+#[instruction(sequence: u64)]
+pub struct RefundNftSourceChain<'info> {
+    #[account(mut)]
+    signer: Signer<'info>,
+    mint: Box<Account<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::authority = signer,
+        associated_token::mint = mint,
+    )]
+    signer_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        mut,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = mint,
+    )]
+    bridge_handler_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"bridge_proof_v2", bridge_handler.key().as_ref(), signer.key().as_ref(), sequence.to_be_bytes().as_ref()],
+        bump = bridge_proof.bump
+    )]
+    bridge_proof: Box<Account<'info, BridgeProofSourceChain>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    // guardians attesting that the message will not be delivered on the target chain
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), BridgeMessage::refund_hash(&bridge_proof.msg_hash).as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl RefundNftSourceChain<'_> {
+    pub fn refund_nft_source_chain(
+        &mut self,
+        sequence: u64,
+        recipient: [u8; 32],
+        metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        BridgeMessage::check_refundable(deadline, Clock::get()?.unix_timestamp as u64)?;
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        // the metadata comes from the caller, it may have been updated since the lock
+        let message = BridgeMessage {
+            source_chain_id: self.bridge_handler.chain.chain_id(),
+            source_emitter: self.bridge_handler.key().to_bytes(),
+            destination_chain_id: self.chain_config.chain_id,
+            destination_emitter: self.chain_config.emitter,
+            sender: self.signer.key().to_bytes(),
+            recipient,
+            sequence,
+            deadline,
+            payload: BridgePayload::Nft {
+                native_mint: self.mint.key(),
+                metadata,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
+        require!(
+            message == self.bridge_proof.msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+        require!(
+            self.bridge_handler_vault.amount == 1,
+            BridgeHandlerError::InsufficientFunds
+        );
+
+        msg!(
+            "refunding nft {:?} to {:?}",
+            self.mint.key(),
+            self.signer.key()
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.bridge_handler_vault.to_account_info(),
+                to: self.signer_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        transfer_checked(ctx, 1, 0)
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, Token2022, TokenInterface};
+
+use crate::states::{Chain, MintMigration, TokenInfo, TokenStatus};
+use crate::utils::initialize_token_2022_metadata;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct RegisterMintMigration<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mint::authority = bridge_handler,
+        mint::token_program = old_token_program
+    )]
+    old_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), old_mint.key().as_ref()],
+        bump = old_token_info.bump
+    )]
+    old_token_info: Box<Account<'info, TokenInfo>>,
+    #[account(
+        init,
+        payer = manager,
+        mint::authority = bridge_handler,
+        mint::token_program = token_program,
+        mint::decimals = old_mint.decimals,
+        extensions::metadata_pointer::authority = bridge_handler,
+        extensions::metadata_pointer::metadata_address = new_mint,
+        seeds = [b"migrated_mint", bridge_handler.key().as_ref(), old_mint.key().as_ref()],
+        bump
+    )]
+    new_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + TokenInfo::INIT_SPACE,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), new_mint.key().as_ref()],
+        bump
+    )]
+    new_token_info: Box<Account<'info, TokenInfo>>,
+    #[account(
+        init,
+        payer = manager,
+        space = 8 + MintMigration::INIT_SPACE,
+        seeds = [b"mint_migration", bridge_handler.key().as_ref(), old_mint.key().as_ref()],
+        bump
+    )]
+    mint_migration: Box<Account<'info, MintMigration>>,
+    old_token_program: Interface<'info, TokenInterface>,
+    token_program: Program<'info, Token2022>,
+    system_program: Program<'info, System>,
+}
+
+impl RegisterMintMigration<'_> {
+    pub fn register_mint_migration(
+        &mut self,
+        bumps: RegisterMintMigrationBumps,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let chain = self.bridge_handler.chain;
+
+        // only wrapped mints are controlled by the bridge handler
+        require!(
+            (chain == Chain::Solana && !self.old_token_info.is_solana_native_token)
+                || (chain == Chain::Solayer && !self.old_token_info.is_solayer_native_token),
+            BridgeHandlerError::InvalidTokenInfo
+        );
+
+        let now = Clock::get()?.unix_timestamp as u64;
+
+        msg!(
+            "registering migration of {:?} to {:?}",
+            self.old_mint.key(),
+            self.new_mint.key()
+        );
+
+        // the replacement takes over the mapping, the legacy mint only drains through
+        // the migration and in-flight deliveries during the delist grace period
+        let (solana_mint, solayer_mint) = match chain {
+            Chain::Solana => (self.new_mint.key(), self.old_token_info.solayer_mint),
+            Chain::Solayer => (self.old_token_info.solana_mint, self.new_mint.key()),
+        };
+        self.new_token_info.init(
+            bumps.new_token_info,
+            solana_mint,
+            solayer_mint,
+            self.old_token_info.is_solana_native_token,
+            self.old_token_info.is_solayer_native_token,
+            self.old_token_info.solana_decimals,
+            self.old_token_info.solayer_decimals,
+            TokenStatus::Active,
+        );
+        self.new_token_info.metadata_nonce = self.old_token_info.metadata_nonce;
+        self.new_token_info.min_transfer_amount = self.old_token_info.min_transfer_amount;
+        self.new_token_info.max_transfer_amount = self.old_token_info.max_transfer_amount;
+
+        if self.old_token_info.status != TokenStatus::Delisted {
+            self.old_token_info.status = TokenStatus::Delisted;
+            self.old_token_info.delisted_at = now;
+        }
+
+        self.mint_migration.bump = bumps.mint_migration;
+        self.mint_migration.old_mint = self.old_mint.key();
+        self.mint_migration.new_mint = self.new_mint.key();
+        self.mint_migration.migrated_supply = 0;
+        self.mint_migration.created_at = now;
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        initialize_token_2022_metadata(
+            self.token_program.to_account_info(),
+            self.new_mint.to_account_info(),
+            self.bridge_handler.to_account_info(),
+            self.manager.to_account_info(),
+            self.system_program.to_account_info(),
+            &signer_seeds[..],
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
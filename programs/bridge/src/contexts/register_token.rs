@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, Token2022};
+
+use crate::states::{
+    Chain, ChainConfig, GuardianInfo, TokenAttestation, TokenInfo, TokenStatus, VerifiedSignatures,
+};
+use crate::utils::initialize_token_2022_metadata;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32], decimal: u8)]
+pub struct RegisterToken<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    // the native chain of the attested token
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    #[account(
+        init,
+        payer = payer,
+        mint::authority = bridge_handler,
+        mint::token_program = token_program,
+        mint::decimals = decimal,
+        extensions::metadata_pointer::authority = bridge_handler,
+        extensions::metadata_pointer::metadata_address = mint,
+        seeds = [b"mint", bridge_handler.key().as_ref(), source_mint.key().as_ref()],
+        bump
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: source_mint won't exist on current chain
+    source_mint: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenInfo::INIT_SPACE,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    token_program: Program<'info, Token2022>,
+    system_program: Program<'info, System>,
+}
+
+impl RegisterToken<'_> {
+    pub fn register_token(
+        &mut self,
+        token_info_bump: u8,
+        msg_hash: [u8; 32],
+        decimal: u8,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require_keys_neq!(
+            self.source_mint.key(),
+            native_mint::ID,
+            BridgeHandlerError::WsolNotSupported
+        );
+
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        let message = TokenAttestation::message_hash(
+            self.chain_config.chain_id,
+            &self.chain_config.emitter,
+            &self.source_mint.key(),
+            decimal,
+            &name,
+            &symbol,
+            &uri,
+        );
+        require!(
+            message == msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+
+        let chain = self.bridge_handler.chain;
+        // the attested token is mapped between the Solana and Solayer pair only
+        TokenInfo::check_counterpart_chain(chain, self.chain_config.chain_id)?;
+
+        let (solana_mint, solayer_mint) = if chain == Chain::Solana {
+            (self.mint.key(), self.source_mint.key())
+        } else {
+            (self.source_mint.key(), self.mint.key())
+        };
+
+        msg!(
+            "registering attested token with decimal: {}, solana_mint: {:?}, solayer_mint: {:?}",
+            decimal,
+            solana_mint,
+            solayer_mint
+        );
+
+        self.token_info.init(
+            token_info_bump,
+            solana_mint,
+            solayer_mint,
+            chain == Chain::Solayer,
+            chain == Chain::Solana,
+            decimal,
+            decimal,
+            TokenStatus::Active,
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        initialize_token_2022_metadata(
+            self.token_program.to_account_info(),
+            self.mint.to_account_info(),
+            self.bridge_handler.to_account_info(),
+            self.payer.to_account_info(),
+            self.system_program.to_account_info(),
+            &signer_seeds[..],
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::BridgeHandlerError,
+    states::{BridgeHandler, FeeExemption},
+};
+
+#[derive(Accounts)]
+pub struct RemoveFeeExemption<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        close = manager,
+        seeds = [b"fee_exemption", bridge_handler.key().as_ref(), fee_exemption.exempt_account.as_ref()],
+        bump = fee_exemption.bump
+    )]
+    fee_exemption: Box<Account<'info, FeeExemption>>,
+}
+
+impl RemoveFeeExemption<'_> {
+    pub fn remove_fee_exemption(&mut self) -> Result<()> {
+        msg!(
+            "removing fee exemption for {:?}",
+            self.fee_exemption.exempt_account
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_FEE_DISCOUNT_BPS,
+    errors::BridgeHandlerError,
+    states::{BridgeHandler, FeeExemption},
+};
+
+#[derive(Accounts)]
+pub struct SetFeeExemption<'info> {
+    #[account(mut)]
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        init_if_needed,
+        payer = manager,
+        space = 8 + FeeExemption::INIT_SPACE,
+        seeds = [b"fee_exemption", bridge_handler.key().as_ref(), exempt_account.key().as_ref()],
+        bump
+    )]
+    fee_exemption: Box<Account<'info, FeeExemption>>,
+    /// CHECK: either a signer wallet or an integrator program id
+    exempt_account: AccountInfo<'info>,
+    system_program: Program<'info, System>,
+}
+
+impl SetFeeExemption<'_> {
+    pub fn set_fee_exemption(&mut self, fee_exemption_bump: u8, discount_bps: u16) -> Result<()> {
+        require!(
+            discount_bps > 0 && discount_bps <= MAX_FEE_DISCOUNT_BPS,
+            BridgeHandlerError::InvalidFeeDiscount
+        );
+
+        self.fee_exemption.bump = fee_exemption_bump;
+        self.fee_exemption.exempt_account = self.exempt_account.key();
+        self.fee_exemption.discount_bps = discount_bps;
+        self.fee_exemption.created_at = Clock::get()?.unix_timestamp as u64;
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+use solana_program::hash::hash;
+
+use crate::states::{
+    Chain, ChainConfig, GuardianInfo, TokenInfo, VerifiedSignatures, TOKEN_METADATA_DOMAIN,
+};
+use crate::utils::{update_wrapped_token_metadata, WrappedTokenMetadataAccounts};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32])]
+pub struct SyncTokenMetadata<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    // the native chain of the token
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    #[account(mut)]
+    /// CHECK: Metaplex metadata of the mint, address is checked when it is used
+    metadata: UncheckedAccount<'info>,
+    token_metadata_program: Program<'info, Metadata>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl SyncTokenMetadata<'_> {
+    pub fn sync_token_metadata(
+        &mut self,
+        msg_hash: [u8; 32],
+        metadata_nonce: u64,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        let chain = self.bridge_handler.chain;
+        TokenInfo::check_counterpart_chain(chain, self.chain_config.chain_id)?;
+
+        // only wrapped mints are controlled by the bridge handler
+        let native_mint = if chain == Chain::Solana && !self.token_info.is_solana_native_token {
+            self.token_info.solayer_mint
+        } else if chain == Chain::Solayer && !self.token_info.is_solayer_native_token {
+            self.token_info.solana_mint
+        } else {
+            return Err(BridgeHandlerError::InvalidTokenInfo.into());
+        };
+
+        // guardians attest metadata with an increasing nonce so older syncs can't be replayed
+        require!(
+            metadata_nonce > self.token_info.metadata_nonce,
+            BridgeHandlerError::StaleMetadataNonce
+        );
+
+        let mut message_data = Vec::new();
+        // bound to the program and to the bridge handlers on both chains, like BridgeMessage
+        message_data.extend_from_slice(TOKEN_METADATA_DOMAIN);
+        message_data.extend_from_slice(&crate::ID.to_bytes());
+        message_data.extend_from_slice(&self.chain_config.chain_id.to_be_bytes());
+        message_data.extend_from_slice(&self.chain_config.emitter);
+        message_data.extend_from_slice(&chain.chain_id().to_be_bytes());
+        message_data.extend_from_slice(&self.bridge_handler.key().to_bytes());
+        message_data.extend_from_slice(&native_mint.to_bytes());
+        message_data.extend_from_slice(&self.mint.key().to_bytes());
+        message_data.extend_from_slice(&metadata_nonce.to_be_bytes());
+        for field in [&name, &symbol, &uri] {
+            message_data.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            message_data.extend_from_slice(field.as_bytes());
+        }
+
+        let message: [u8; 32] = hash(message_data.as_ref()).to_bytes();
+        require!(
+            message == msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+
+        msg!(
+            "syncing metadata of {:?} from {:?}, nonce: {}",
+            self.mint.key(),
+            native_mint,
+            metadata_nonce
+        );
+        self.token_info.metadata_nonce = metadata_nonce;
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        update_wrapped_token_metadata(
+            WrappedTokenMetadataAccounts {
+                payer: self.payer.to_account_info(),
+                mint: self.mint.to_account_info(),
+                metadata: self.metadata.to_account_info(),
+                bridge_handler: self.bridge_handler.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                token_metadata_program: self.token_metadata_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            &signer_seeds[..],
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::states::{
+    BridgeMessage, BridgePayload, BridgeProof, ChainConfig, DeliveryBitmap, GuardianInfo,
+    NftMetadata, VerifiedSignatures,
+};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+#[instruction(msg_hash: [u8; 32], sequence: u64, source_tx_id: [u8; 64], sender: [u8; 32])]
+pub struct UnlockNftTargetChain<'info> {
+    // the operator, or anyone claiming a delivery the guardians have attested
+    #[account(mut)]
+    payer: Signer<'info>,
+    mint: Box<Account<'info, Mint>>,
+    /// CHECKED: checks will be performed agsint signature with hash
+    recipient: AccountInfo<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::authority = recipient,
+        associated_token::mint = mint,
+    )]
+    recipient_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+    #[account(
+        mut,
+        associated_token::authority = bridge_handler,
+        associated_token::mint = mint,
+    )]
+    bridge_handler_vault: Box<Account<'info, TokenAccount>>,
+    // optional per-transfer receipt, replays are rejected by the delivery bitmap
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + BridgeProof::INIT_SPACE,
+        seeds = [b"bridge_proof", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), sequence.to_be_bytes().as_ref()],
+        bump
+    )]
+    bridge_proof: Option<Box<Account<'info, BridgeProof>>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DeliveryBitmap::INIT_SPACE,
+        seeds = [b"delivery_bitmap", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref(), chain_config.emitter.as_ref(), sender.as_ref(), DeliveryBitmap::page(sequence).to_be_bytes().as_ref()],
+        bump
+    )]
+    delivery_bitmap: Box<Account<'info, DeliveryBitmap>>,
+    // guardian set attesting messages from the remote chain
+    #[account(address = chain_config.guardian_info @ BridgeHandlerError::InvalidGuardianInfo)]
+    guardian_info: Box<Account<'info, GuardianInfo>>,
+    #[account(
+        mut,
+        close = signatures_payer,
+        seeds = [b"verified_signatures", bridge_handler.key().as_ref(), msg_hash.as_ref()],
+        bump = verified_signatures.bump
+    )]
+    verified_signatures: Box<Account<'info, VerifiedSignatures>>,
+    #[account(
+        mut,
+        address = verified_signatures.payer @ BridgeHandlerError::InvalidSignaturesPayer
+    )]
+    /// CHECK: only receives the rent of the guardian signatures
+    signatures_payer: AccountInfo<'info>,
+    token_program: Program<'info, Token>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    system_program: Program<'info, System>,
+}
+
+impl UnlockNftTargetChain<'_> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn unlock_nft_target_chain(
+        &mut self,
+        bumps: UnlockNftTargetChainBumps,
+        msg_hash: [u8; 32],
+        sequence: u64,
+        source_tx_id: [u8; 64],
+        sender: [u8; 32],
+        nft_metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        require!(!self.bridge_handler.pause, BridgeHandlerError::BridgePaused);
+        require!(!self.chain_config.pause, BridgeHandlerError::ChainPaused);
+        require!(
+            !BridgeMessage::is_expired(deadline, Clock::get()?.unix_timestamp as u64),
+            BridgeHandlerError::TransferExpired
+        );
+
+        self.verified_signatures.check_guardian_threshold(
+            self.guardian_info.guardians.len(),
+            self.bridge_handler.guardian_threshold,
+        )?;
+
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&sender)?;
+        let message = BridgeMessage {
+            source_chain_id: self.chain_config.chain_id,
+            source_emitter: self.chain_config.emitter,
+            destination_chain_id: self.bridge_handler.chain.chain_id(),
+            destination_emitter: self.bridge_handler.key().to_bytes(),
+            sender,
+            recipient: self.recipient.key().to_bytes(),
+            sequence,
+            deadline,
+            payload: BridgePayload::Nft {
+                native_mint: self.mint.key(),
+                metadata: nft_metadata,
+            },
+        }
+        .hash(self.chain_config.chain_kind)?;
+        require!(
+            message == msg_hash,
+            BridgeHandlerError::InvalidGuardianSignatureMessage
+        );
+        require!(
+            self.bridge_handler_vault.amount == 1,
+            BridgeHandlerError::InsufficientFunds
+        );
+
+        msg!("unlock nft {:?} to recipient", self.mint.key());
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.bridge_handler_vault.to_account_info(),
+                to: self.recipient_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.bridge_handler.to_account_info(),
+            },
+            &signer_seeds[..],
+        );
+        transfer_checked(ctx, 1, 0)?;
+
+        let recipient = self.recipient.key();
+        let payer = self.payer.key();
+        let receipt = self
+            .bridge_proof
+            .as_deref_mut()
+            .map(|bridge_proof| &mut **bridge_proof)
+            .zip(bumps.bridge_proof);
+        self.delivery_bitmap.record_delivery(
+            bumps.delivery_bitmap,
+            sequence,
+            receipt,
+            message,
+            source_tx_id,
+            recipient,
+            payer,
+        )?;
+
+        Ok(())
+    }
+}
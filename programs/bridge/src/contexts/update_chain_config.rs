@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{ChainConfig, FixedFeeInfo};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct UpdateChainConfig<'info> {
+    manager: Signer<'info>,
+    #[account(
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        seeds = [b"chain_config", bridge_handler.key().as_ref(), chain_config.chain_id.to_be_bytes().as_ref()],
+        bump = chain_config.bump
+    )]
+    chain_config: Box<Account<'info, ChainConfig>>,
+}
+
+impl UpdateChainConfig<'_> {
+    pub fn update_chain_config(
+        &mut self,
+        emitter: [u8; 32],
+        fee_info: FixedFeeInfo,
+        instant_bridge_cap: u64,
+    ) -> Result<()> {
+        self.chain_config
+            .chain_kind
+            .check_universal_address(&emitter)?;
+
+        msg!(
+            "updating chain config for chain id {}",
+            self.chain_config.chain_id
+        );
+
+        self.chain_config.emitter = emitter;
+        self.chain_config.fee_info = fee_info;
+        // the new cap also applies to what is left of the current epoch
+        self.chain_config.instant_bridge_cap_per_epoch = instant_bridge_cap;
+        self.chain_config.instant_bridge_cap_remained_dollar = instant_bridge_cap;
+        Ok(())
+    }
+}
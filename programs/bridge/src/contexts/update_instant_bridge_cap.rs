@@ -15,6 +15,7 @@ pub struct UpdateInstantBridgeCap<'info> {
 }
 
 impl UpdateInstantBridgeCap<'_> {
+    // only applies to chain configs added afterwards, see update_chain_config
     pub fn update_instant_bridge_cap(&mut self, instant_bridge_cap: u64) -> Result<()> {
         self.bridge_handler.instant_bridge_cap_remained_dollar = instant_bridge_cap;
         Ok(())
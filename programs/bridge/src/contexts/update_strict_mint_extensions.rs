@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct UpdateStrictMintExtensions<'info> {
+    manager: Signer<'info>,
+    #[account(
+        mut,
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+}
+
+impl UpdateStrictMintExtensions<'_> {
+    pub fn update_strict_mint_extensions(&mut self, strict_mint_extensions: bool) -> Result<()> {
+        self.bridge_handler.strict_mint_extensions = strict_mint_extensions;
+        Ok(())
+    }
+}
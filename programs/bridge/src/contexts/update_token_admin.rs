@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct UpdateTokenAdmin<'info> {
+    manager: Signer<'info>,
+    #[account(
+        mut,
+        has_one = manager @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    /// CHECK: no check needed
+    new_token_admin: AccountInfo<'info>,
+}
+
+impl UpdateTokenAdmin<'_> {
+    pub fn update_token_admin(&mut self) -> Result<()> {
+        self.bridge_handler.token_admin = self.new_token_admin.key();
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::Metadata,
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::states::{Chain, TokenInfo};
+use crate::utils::{update_wrapped_token_metadata, WrappedTokenMetadataAccounts};
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    #[account(mut)]
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    #[account(mut)]
+    /// CHECK: Metaplex metadata of the mint, address is checked when it is used
+    metadata: UncheckedAccount<'info>,
+    token_metadata_program: Program<'info, Metadata>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl UpdateTokenMetadata<'_> {
+    pub fn update_token_metadata(
+        &mut self,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let chain = self.bridge_handler.chain;
+
+        // only wrapped mints are controlled by the bridge handler
+        require!(
+            (chain == Chain::Solana && !self.token_info.is_solana_native_token)
+                || (chain == Chain::Solayer && !self.token_info.is_solayer_native_token),
+            BridgeHandlerError::InvalidTokenInfo
+        );
+
+        msg!(
+            "updating metadata of {:?} to name: {}, symbol: {}, uri: {}",
+            self.mint.key(),
+            name,
+            symbol,
+            uri
+        );
+
+        let bump = [self.bridge_handler.bump];
+        let init_nonce_bytes = self.bridge_handler.init_nonce.to_be_bytes();
+        let signer_seeds: [&[&[u8]]; 1] =
+            [&[b"bridge_handler", init_nonce_bytes.as_ref(), &bump][..]];
+
+        update_wrapped_token_metadata(
+            WrappedTokenMetadataAccounts {
+                payer: self.token_admin.to_account_info(),
+                mint: self.mint.to_account_info(),
+                metadata: self.metadata.to_account_info(),
+                bridge_handler: self.bridge_handler.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+                token_metadata_program: self.token_metadata_program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            &signer_seeds[..],
+            name,
+            symbol,
+            uri,
+        )
+    }
+}
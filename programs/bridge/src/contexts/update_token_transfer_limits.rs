@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::states::TokenInfo;
+use crate::{errors::BridgeHandlerError, states::BridgeHandler};
+
+#[derive(Accounts)]
+pub struct UpdateTokenTransferLimits<'info> {
+    token_admin: Signer<'info>,
+    #[account(
+        has_one = token_admin @ BridgeHandlerError::Unauthorized,
+        seeds = [b"bridge_handler", bridge_handler.init_nonce.to_be_bytes().as_ref()],
+        bump = bridge_handler.bump
+    )]
+    bridge_handler: Box<Account<'info, BridgeHandler>>,
+    #[account(mint::token_program = token_program)]
+    mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"token_info", bridge_handler.key().as_ref(), mint.key().as_ref()],
+        bump = token_info.bump
+    )]
+    token_info: Box<Account<'info, TokenInfo>>,
+    token_program: Interface<'info, TokenInterface>,
+}
+
+impl UpdateTokenTransferLimits<'_> {
+    pub fn update_token_transfer_limits(
+        &mut self,
+        min_transfer_amount: u64,
+        max_transfer_amount: u64,
+    ) -> Result<()> {
+        require!(
+            max_transfer_amount == 0 || min_transfer_amount <= max_transfer_amount,
+            BridgeHandlerError::InvalidTransferLimits
+        );
+
+        msg!(
+            "updating transfer limits of {:?} to min: {}, max: {}",
+            self.mint.key(),
+            min_transfer_amount,
+            max_transfer_amount
+        );
+
+        // both limits are in message precision, see TokenInfo::message_decimals
+        self.token_info.min_transfer_amount = min_transfer_amount;
+        self.token_info.max_transfer_amount = max_transfer_amount;
+        Ok(())
+    }
+}
@@ -79,6 +79,9 @@ impl VerifySignature<'_> {
         msg!("Guardian signers: {:?}", signers);
         verify_ed25519_ix(&self.ix_sysvar, signers, msg_hash)?;
 
+        if self.verified_signatures.pubkey_index.is_empty() {
+            self.verified_signatures.payer = self.operator.key();
+        }
         self.verified_signatures.bump = bump.verified_signatures;
         self.verified_signatures.pubkey_index.extend(signer_indexes);
         self.verified_signatures.created_at = Clock::get()?.unix_timestamp as u64;
@@ -127,4 +127,106 @@ pub enum BridgeHandlerError {
 
     #[msg("instant bridge cap exceeded")]
     InstantBridgeCapExceeded,
+
+    #[msg("fee exemption does not apply to signer or calling program")]
+    InvalidFeeExemption,
+
+    #[msg("invalid fee discount")]
+    InvalidFeeDiscount,
+
+    #[msg("unsupported mint extension")]
+    UnsupportedMintExtension,
+
+    #[msg("invalid token metadata")]
+    InvalidTokenMetadata,
+
+    #[msg("stale metadata nonce")]
+    StaleMetadataNonce,
+
+    #[msg("wrapped token can't be attested")]
+    WrappedTokenAttestation,
+
+    #[msg("invalid token status")]
+    InvalidTokenStatus,
+
+    #[msg("token delisted")]
+    TokenDelisted,
+
+    #[msg("too many mapping migrations")]
+    TooManyMappingMigrations,
+
+    #[msg("invalid counterpart mint")]
+    InvalidCounterpartMint,
+
+    #[msg("invalid nft")]
+    InvalidNft,
+
+    #[msg("nft already bridged")]
+    NftAlreadyBridged,
+
+    #[msg("token is not in pool mode")]
+    NotPoolModeToken,
+
+    #[msg("missing wsol accounts")]
+    MissingWsolAccounts,
+
+    #[msg("wsol must be bridged through the sol path")]
+    WsolNotSupported,
+
+    #[msg("transfer amount below the token minimum")]
+    TransferAmountTooSmall,
+
+    #[msg("transfer amount above the token maximum")]
+    TransferAmountTooLarge,
+
+    #[msg("invalid token transfer limits")]
+    InvalidTransferLimits,
+
+    #[msg("chain paused")]
+    ChainPaused,
+
+    #[msg("invalid guardian info")]
+    InvalidGuardianInfo,
+
+    #[msg("invalid universal address")]
+    InvalidUniversalAddress,
+
+    #[msg("transfer already delivered")]
+    AlreadyDelivered,
+
+    #[msg("finality period not elapsed")]
+    FinalityPeriodNotElapsed,
+
+    #[msg("refund deadline not reached")]
+    RefundDeadlineNotReached,
+
+    #[msg("transfer without a deadline can't be refunded")]
+    RefundWithoutDeadline,
+
+    #[msg("missing bridge handler vault")]
+    MissingBridgeHandlerVault,
+
+    #[msg("transfer deadline passed")]
+    TransferExpired,
+
+    #[msg("invalid account layout")]
+    InvalidAccountLayout,
+
+    #[msg("account not found in instruction accounts")]
+    AccountNotInInstruction,
+
+    #[msg("sender sequence overflow")]
+    SequenceOverflow,
+
+    #[msg("invalid target decimals")]
+    InvalidTargetDecimals,
+
+    #[msg("asset is not bridged with this chain")]
+    InvalidCounterpartChain,
+
+    #[msg("invalid receipt payer")]
+    InvalidReceiptPayer,
+
+    #[msg("invalid signatures payer")]
+    InvalidSignaturesPayer,
 }
@@ -1,7 +1,7 @@
 #![allow(unexpected_cfgs)]
 
 use crate::errors::BridgeHandlerError;
-use crate::states::{Chain, FixedFeeInfo};
+use crate::states::{Chain, ChainKind, FixedFeeInfo, NftMetadata};
 use anchor_lang::prelude::*;
 use contexts::*;
 
@@ -31,12 +31,172 @@ pub mod bridge_program {
     pub fn add_token(
         ctx: Context<AddToken>,
         decimal: u8,
+        source_decimal: u8,
         name: Option<String>,
         symbol: Option<String>,
         uri: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts.add_token(
+            ctx.bumps.token_info,
+            decimal,
+            source_decimal,
+            name,
+            symbol,
+            uri,
+        )?;
+        Ok(())
+    }
+
+    pub fn add_token_2022(
+        ctx: Context<AddToken2022>,
+        decimal: u8,
+        source_decimal: u8,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.add_token_2022(
+            ctx.bumps.token_info,
+            decimal,
+            source_decimal,
+            name,
+            symbol,
+            uri,
+        )?;
+        Ok(())
+    }
+
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.update_token_metadata(name, symbol, uri)?;
+        Ok(())
+    }
+
+    pub fn sync_token_metadata(
+        ctx: Context<SyncTokenMetadata>,
+        msg_hash: [u8; 32],
+        metadata_nonce: u64,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts
+            .sync_token_metadata(msg_hash, metadata_nonce, name, symbol, uri)?;
+        Ok(())
+    }
+
+    pub fn attest_token(ctx: Context<AttestToken>) -> Result<()> {
+        ctx.accounts.attest_token(ctx.bumps)?;
+        Ok(())
+    }
+
+    pub fn register_token(
+        ctx: Context<RegisterToken>,
+        msg_hash: [u8; 32],
+        decimal: u8,
+        name: String,
+        symbol: String,
+        uri: String,
     ) -> Result<()> {
         ctx.accounts
-            .add_token(ctx.bumps.token_info, decimal, name, symbol, uri)?;
+            .register_token(ctx.bumps.token_info, msg_hash, decimal, name, symbol, uri)?;
+        Ok(())
+    }
+
+    pub fn propose_token(ctx: Context<ProposeToken>, target_decimal: u8) -> Result<()> {
+        ctx.accounts
+            .propose_token(ctx.bumps.token_info, target_decimal)?;
+        Ok(())
+    }
+
+    pub fn propose_pool_token(
+        ctx: Context<ProposeToken>,
+        target_mint: Pubkey,
+        target_decimal: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .propose_pool_token(ctx.bumps.token_info, target_mint, target_decimal)?;
+        Ok(())
+    }
+
+    pub fn activate_token(ctx: Context<ActivateToken>) -> Result<()> {
+        ctx.accounts.activate_token()?;
+        Ok(())
+    }
+
+    pub fn delist_token(ctx: Context<DelistToken>) -> Result<()> {
+        ctx.accounts.delist_token()?;
+        Ok(())
+    }
+
+    pub fn update_token_transfer_limits(
+        ctx: Context<UpdateTokenTransferLimits>,
+        min_transfer_amount: u64,
+        max_transfer_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_token_transfer_limits(min_transfer_amount, max_transfer_amount)?;
+        Ok(())
+    }
+
+    pub fn migrate_token_mapping(
+        ctx: Context<MigrateTokenMapping>,
+        new_counterpart_mint: Pubkey,
+        new_counterpart_decimal: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .migrate_token_mapping(new_counterpart_mint, new_counterpart_decimal)?;
+        Ok(())
+    }
+
+    pub fn migrate_token_info(
+        ctx: Context<MigrateTokenInfo>,
+        counterpart_decimal: u8,
+    ) -> Result<()> {
+        ctx.accounts.migrate_token_info(counterpart_decimal)?;
+        Ok(())
+    }
+
+    pub fn init_liquidity_pool(ctx: Context<InitLiquidityPool>) -> Result<()> {
+        ctx.accounts.init_liquidity_pool(ctx.bumps.liquidity_pool)?;
+        Ok(())
+    }
+
+    pub fn deposit_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ManageLiquidity<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .deposit_liquidity(amount, ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn withdraw_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ManageLiquidity<'info>>,
+        shares: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .withdraw_liquidity(shares, ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn register_mint_migration(
+        ctx: Context<RegisterMintMigration>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts
+            .register_mint_migration(ctx.bumps, name, symbol, uri)?;
+        Ok(())
+    }
+
+    pub fn migrate_wrapped_token(ctx: Context<MigrateWrappedToken>, amount: u64) -> Result<()> {
+        ctx.accounts.migrate_wrapped_token(amount)?;
         Ok(())
     }
 
@@ -60,87 +220,295 @@ pub mod bridge_program {
         Ok(())
     }
 
-    pub fn bridge_asset_source_chain(
-        ctx: Context<BridgeAssetSourceChain>,
-        bridge_proof_nonce: u64,
+    pub fn add_chain_config(
+        ctx: Context<AddChainConfig>,
+        chain_id: u16,
+        chain_kind: ChainKind,
+        emitter: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts
+            .add_chain_config(ctx.bumps.chain_config, chain_id, chain_kind, emitter)?;
+        Ok(())
+    }
+
+    pub fn update_chain_config(
+        ctx: Context<UpdateChainConfig>,
+        emitter: [u8; 32],
+        fee_info: FixedFeeInfo,
+        instant_bridge_cap: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_chain_config(emitter, fee_info, instant_bridge_cap)?;
+        Ok(())
+    }
+
+    pub fn pause_chain(ctx: Context<PauseChain>) -> Result<()> {
+        ctx.accounts.pause_chain()?;
+        Ok(())
+    }
+
+    pub fn unpause_chain(ctx: Context<PauseChain>) -> Result<()> {
+        ctx.accounts.unpause_chain()?;
+        Ok(())
+    }
+
+    pub fn bridge_asset_source_chain<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeAssetSourceChain<'info>>,
         amount: u64,
-        recipient: Pubkey,
-        target_mint: Pubkey,
+        recipient: [u8; 32],
         additional_sol_gas: u64,
+        deadline: u64,
     ) -> Result<u64> {
-        let nonce = ctx.accounts.bridge_asset_source_chain(
-            ctx.bumps.token_info,
+        let (nonce, message_amount) = ctx.accounts.bridge_asset_source_chain(
+            &ctx.bumps,
             amount,
             recipient,
-            target_mint,
             additional_sol_gas,
+            ctx.remaining_accounts,
         )?;
         ctx.accounts.issue_bridge_proof(
             ctx.bumps.bridge_proof,
-            bridge_proof_nonce,
-            amount,
+            nonce,
+            message_amount,
             recipient,
+            additional_sol_gas,
+            deadline,
         )?;
         Ok(nonce)
     }
 
     pub fn bridge_asset_source_chain_sol(
         ctx: Context<BridgeAssetSourceChainSol>,
-        bridge_proof_nonce: u64,
         amount: u64,
-        recipient: Pubkey,
+        recipient: [u8; 32],
+        from_wsol: bool,
+        deliver_as_wsol: bool,
+        deadline: u64,
     ) -> Result<u64> {
         let nonce = ctx
             .accounts
-            .bridge_asset_source_chain_sol(amount, recipient)?;
-        ctx.accounts
-            .issue_bridge_proof(ctx.bumps, bridge_proof_nonce, amount, recipient)?;
+            .bridge_asset_source_chain_sol(&ctx.bumps, amount, recipient, from_wsol)?;
+        ctx.accounts.issue_bridge_proof(
+            ctx.bumps,
+            nonce,
+            amount,
+            recipient,
+            deliver_as_wsol,
+            deadline,
+        )?;
         Ok(nonce)
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn bridge_asset_target_chain(
-        ctx: Context<BridgeAssetTargetChain>,
+    pub fn bridge_asset_target_chain<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeAssetTargetChain<'info>>,
         msg_hash: [u8; 32],
+        sequence: u64,
         source_tx_id: [u8; 64],
-        sender: Pubkey,
+        sender: [u8; 32],
         source_mint: Pubkey,
         receive_amount: u64,
-        nonce: u64,
         additional_sol_gas: u64,
+        deadline: u64,
     ) -> Result<()> {
         ctx.accounts.bridge_asset_target_chain(
             ctx.bumps,
             msg_hash,
+            sequence,
             source_tx_id,
             sender,
             source_mint,
             receive_amount,
-            nonce,
             additional_sol_gas,
+            deadline,
+            ctx.remaining_accounts,
         )?;
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn bridge_asset_target_chain_sol(
         ctx: Context<BridgeAssetTargetChainSol>,
         msg_hash: [u8; 32],
+        sequence: u64,
         source_tx_id: [u8; 64],
-        sender: Pubkey,
+        sender: [u8; 32],
         receive_amount: u64,
-        nonce: u64,
+        deliver_as_wsol: bool,
+        deadline: u64,
     ) -> Result<()> {
         ctx.accounts.bridge_asset_target_chain_sol(
             ctx.bumps,
             msg_hash,
+            sequence,
             source_tx_id,
             sender,
             receive_amount,
-            nonce,
+            deliver_as_wsol,
+            deadline,
+        )?;
+        Ok(())
+    }
+
+    pub fn init_nft_collection(
+        ctx: Context<InitNftCollection>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        ctx.accounts.init_nft_collection(name, symbol, uri)?;
+        Ok(())
+    }
+
+    pub fn lock_nft_source_chain(
+        ctx: Context<LockNftSourceChain>,
+        recipient: [u8; 32],
+        deadline: u64,
+    ) -> Result<u64> {
+        ctx.accounts
+            .lock_nft_source_chain(ctx.bumps, recipient, deadline)
+    }
+
+    pub fn burn_nft_source_chain(
+        ctx: Context<BurnNftSourceChain>,
+        recipient: [u8; 32],
+        deadline: u64,
+    ) -> Result<u64> {
+        ctx.accounts
+            .burn_nft_source_chain(ctx.bumps, recipient, deadline)
+    }
+
+    pub fn unlock_nft_target_chain(
+        ctx: Context<UnlockNftTargetChain>,
+        msg_hash: [u8; 32],
+        sequence: u64,
+        source_tx_id: [u8; 64],
+        sender: [u8; 32],
+        nft_metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        ctx.accounts.unlock_nft_target_chain(
+            ctx.bumps,
+            msg_hash,
+            sequence,
+            source_tx_id,
+            sender,
+            nft_metadata,
+            deadline,
+        )?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn mint_nft_target_chain(
+        ctx: Context<MintNftTargetChain>,
+        msg_hash: [u8; 32],
+        sequence: u64,
+        source_tx_id: [u8; 64],
+        native_mint: Pubkey,
+        sender: [u8; 32],
+        nft_metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        ctx.accounts.mint_nft_target_chain(
+            ctx.bumps,
+            msg_hash,
+            sequence,
+            source_tx_id,
+            native_mint,
+            sender,
+            nft_metadata,
+            deadline,
+        )?;
+        Ok(())
+    }
+
+    pub fn refund_bridge_asset_source_chain<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundBridgeAssetSourceChain<'info>>,
+        sequence: u64,
+        recipient: [u8; 32],
+        target_mint: Pubkey,
+        amount: u64,
+        additional_sol_gas: u64,
+        deadline: u64,
+    ) -> Result<()> {
+        ctx.accounts.refund_bridge_asset_source_chain(
+            ctx.bumps,
+            sequence,
+            recipient,
+            target_mint,
+            amount,
+            additional_sol_gas,
+            deadline,
+            ctx.remaining_accounts,
         )?;
         Ok(())
     }
 
+    pub fn refund_bridge_asset_source_chain_sol(
+        ctx: Context<RefundBridgeAssetSourceChainSol>,
+        sequence: u64,
+        recipient: [u8; 32],
+        amount: u64,
+        deliver_as_wsol: bool,
+        deadline: u64,
+    ) -> Result<()> {
+        ctx.accounts.refund_bridge_asset_source_chain_sol(
+            ctx.bumps,
+            sequence,
+            recipient,
+            amount,
+            deliver_as_wsol,
+            deadline,
+        )?;
+        Ok(())
+    }
+
+    pub fn refund_nft_source_chain(
+        ctx: Context<RefundNftSourceChain>,
+        sequence: u64,
+        recipient: [u8; 32],
+        nft_metadata: NftMetadata,
+        deadline: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .refund_nft_source_chain(sequence, recipient, nft_metadata, deadline)?;
+        Ok(())
+    }
+
+    pub fn close_bridge_proof_source_chain(
+        ctx: Context<CloseBridgeProofSourceChain>,
+    ) -> Result<()> {
+        ctx.accounts.close_bridge_proof_source_chain()?;
+        Ok(())
+    }
+
+    pub fn close_bridge_proof(
+        ctx: Context<CloseBridgeProof>,
+        sender: [u8; 32],
+        sequence: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .close_bridge_proof(ctx.bumps, sender, sequence)?;
+        Ok(())
+    }
+
+    pub fn close_legacy_bridge_proof(
+        ctx: Context<CloseLegacyBridgeProof>,
+        source_tx_id: [u8; 64],
+    ) -> Result<()> {
+        ctx.accounts.close_legacy_bridge_proof(source_tx_id)?;
+        Ok(())
+    }
+
+    pub fn migrate_verified_signatures(
+        ctx: Context<MigrateVerifiedSignatures>,
+        msg_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.migrate_verified_signatures(msg_hash)?;
+        Ok(())
+    }
+
     pub fn add_guardian(ctx: Context<AddGuardian>) -> Result<()> {
         ctx.accounts.add_guardian()?;
         Ok(())
@@ -192,8 +560,59 @@ pub mod bridge_program {
         Ok(())
     }
 
+    pub fn migrate_bridge_handler(ctx: Context<MigrateBridgeHandler>) -> Result<()> {
+        ctx.accounts.migrate_bridge_handler()?;
+        Ok(())
+    }
+
+    pub fn migrate_sol_vault(ctx: Context<MigrateSolVault>) -> Result<()> {
+        ctx.accounts.migrate_sol_vault()?;
+        Ok(())
+    }
+
     pub fn update_manager(ctx: Context<UpdateManager>) -> Result<()> {
         ctx.accounts.update_manager()?;
         Ok(())
     }
+
+    pub fn update_token_admin(ctx: Context<UpdateTokenAdmin>) -> Result<()> {
+        ctx.accounts.update_token_admin()?;
+        Ok(())
+    }
+
+    pub fn update_strict_mint_extensions(
+        ctx: Context<UpdateStrictMintExtensions>,
+        strict_mint_extensions: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_strict_mint_extensions(strict_mint_extensions)?;
+        Ok(())
+    }
+
+    pub fn set_fee_exemption(ctx: Context<SetFeeExemption>, discount_bps: u16) -> Result<()> {
+        ctx.accounts
+            .set_fee_exemption(ctx.bumps.fee_exemption, discount_bps)?;
+        Ok(())
+    }
+
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        ctx.accounts.remove_fee_exemption()?;
+        Ok(())
+    }
+
+    pub fn quote_bridge(
+        ctx: Context<QuoteBridge>,
+        amount: u64,
+        additional_sol_gas: u64,
+    ) -> Result<BridgeQuote> {
+        ctx.accounts.quote_bridge(amount, additional_sol_gas)
+    }
+
+    pub fn quote_delivery(
+        ctx: Context<QuoteDelivery>,
+        source_mint: Pubkey,
+        message_amount: u64,
+    ) -> Result<DeliveryQuote> {
+        ctx.accounts.quote_delivery(source_mint, message_amount)
+    }
 }
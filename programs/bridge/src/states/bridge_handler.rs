@@ -6,16 +6,19 @@ pub struct BridgeHandler {
     pub bump: u8,
     pub init_nonce: u64,
     pub pause: bool,
-    pub nonce: u64,
+    pub nonce: u64, // deprecated, deposit sequences are allocated per sender in SenderSequence
     pub chain: Chain,
     pub fee_vault: Pubkey,
     pub manager: Pubkey,
     pub operator: Pubkey,
     pub guardian_info: Pubkey,
     pub guardian_threshold: u8,
-    pub instant_bridge_cap_remained_dollar: u64,
-    pub instant_bridge_cap_epoch: u64,
+    // defaults for new chain configs, every remote chain keeps its own fees and cap
+    pub instant_bridge_cap_remained_dollar: u64, // cap per epoch of new chain configs
+    pub instant_bridge_cap_epoch: u64,           // unused since the cap moved to ChainConfig
     pub fee_info: FixedFeeInfo,
+    pub strict_mint_extensions: bool, // reject mints with extensions the bridge can't account for
+    pub token_admin: Pubkey,
 }
 
 #[derive(InitSpace, Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
@@ -24,6 +27,21 @@ pub enum Chain {
     Solayer = 2,
 }
 
+impl Chain {
+    // id of the chain in bridge messages, remote chains are registered by id in ChainConfig
+    pub fn chain_id(&self) -> u16 {
+        *self as u16
+    }
+
+    // the other chain of the Solana and Solayer pair that token mappings are made between
+    pub fn counterpart(&self) -> Chain {
+        match self {
+            Chain::Solana => Chain::Solayer,
+            Chain::Solayer => Chain::Solana,
+        }
+    }
+}
+
 #[derive(InitSpace, Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub struct FixedFeeInfo {
     // all in form of lamports
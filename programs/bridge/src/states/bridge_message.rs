@@ -0,0 +1,313 @@
+use anchor_lang::prelude::*;
+use solana_program::hash;
+
+use crate::constants::REFUND_CLOCK_SKEW_SECONDS;
+use crate::errors::BridgeHandlerError;
+use crate::states::{ChainKind, NftMetadata};
+
+pub const BRIDGE_MESSAGE_VERSION: u8 = 2;
+// separates bridge messages from the other payloads guardians sign
+pub const BRIDGE_MESSAGE_DOMAIN: &[u8] = b"bridge_message";
+// guardians attest a delivery back to the source chain under this domain
+pub const BRIDGE_DELIVERY_DOMAIN: &[u8] = b"bridge_delivery";
+// guardians attest that an undelivered message can be refunded on the source chain
+pub const BRIDGE_REFUND_DOMAIN: &[u8] = b"bridge_refund";
+// guardians attest a native token so it can be registered on the other chains
+pub const TOKEN_ATTESTATION_DOMAIN: &[u8] = b"token_attestation";
+// guardians attest the metadata of a native token for its wrapped mint
+pub const TOKEN_METADATA_DOMAIN: &[u8] = b"token_metadata";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BridgePayload {
+    Token {
+        source_mint: Pubkey,
+        target_mint: Pubkey,
+        amount: u64, // in message precision
+        additional_sol_gas: u64,
+    },
+    Sol {
+        amount: u64,
+        deliver_as_wsol: bool,
+    },
+    Nft {
+        native_mint: Pubkey,
+        metadata: NftMetadata,
+    },
+}
+
+// The message guardians sign for every transfer, encoded the same way by the source and
+// the target instruction. The domain binds it to the program and to the bridge handlers
+// on both chains, so attestations can't be replayed across deployments or init nonces.
+// Deliveries are keyed by (source chain, emitter, sender, sequence) on the target chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BridgeMessage {
+    pub source_chain_id: u16,
+    pub source_emitter: [u8; 32],
+    pub destination_chain_id: u16,
+    pub destination_emitter: [u8; 32],
+    pub sender: [u8; 32],
+    pub recipient: [u8; 32],
+    pub sequence: u64, // allocated per sender by the source bridge handler
+    pub deadline: u64, // unix timestamp after which the target rejects delivery, 0 for none
+    pub payload: BridgePayload,
+}
+
+impl BridgeMessage {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut message_data = Vec::new();
+        message_data.extend_from_slice(BRIDGE_MESSAGE_DOMAIN);
+        message_data.push(BRIDGE_MESSAGE_VERSION);
+        message_data.extend_from_slice(&crate::ID.to_bytes());
+        message_data.extend_from_slice(&self.source_chain_id.to_be_bytes());
+        message_data.extend_from_slice(&self.source_emitter);
+        message_data.extend_from_slice(&self.destination_chain_id.to_be_bytes());
+        message_data.extend_from_slice(&self.destination_emitter);
+        message_data.extend_from_slice(&self.sender);
+        message_data.extend_from_slice(&self.recipient);
+        message_data.extend_from_slice(&self.sequence.to_be_bytes());
+        message_data.extend_from_slice(&self.deadline.to_be_bytes());
+
+        match &self.payload {
+            BridgePayload::Token {
+                source_mint,
+                target_mint,
+                amount,
+                additional_sol_gas,
+            } => {
+                message_data.push(0);
+                message_data.extend_from_slice(&source_mint.to_bytes());
+                message_data.extend_from_slice(&target_mint.to_bytes());
+                message_data.extend_from_slice(&amount.to_be_bytes());
+                message_data.extend_from_slice(&additional_sol_gas.to_be_bytes());
+            }
+            BridgePayload::Sol {
+                amount,
+                deliver_as_wsol,
+            } => {
+                message_data.push(1);
+                message_data.extend_from_slice(&amount.to_be_bytes());
+                message_data.push(*deliver_as_wsol as u8);
+            }
+            BridgePayload::Nft {
+                native_mint,
+                metadata,
+            } => {
+                message_data.push(2);
+                message_data.extend_from_slice(&native_mint.to_bytes());
+                metadata.encode(&mut message_data)?;
+            }
+        }
+        Ok(message_data)
+    }
+
+    // EVM chains verify keccak256, see ChainKind
+    pub fn hash(&self, chain_kind: ChainKind) -> Result<[u8; 32]> {
+        Ok(chain_kind.hash_message(&self.encode()?))
+    }
+
+    pub fn is_expired(deadline: u64, now: u64) -> bool {
+        deadline != 0 && now > deadline
+    }
+
+    // A refund is only safe once the target can no longer deliver the message, which the
+    // program can only tell from the deadline. The margin covers the clocks of the two
+    // chains drifting apart.
+    pub fn check_refundable(deadline: u64, now: u64) -> Result<()> {
+        require!(deadline != 0, BridgeHandlerError::RefundWithoutDeadline);
+        let refundable_after = deadline
+            .checked_add(REFUND_CLOCK_SKEW_SECONDS)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        require!(
+            now > refundable_after,
+            BridgeHandlerError::RefundDeadlineNotReached
+        );
+        Ok(())
+    }
+
+    // what guardians sign on the source chain once the message with `msg_hash` is delivered
+    pub fn delivery_hash(msg_hash: &[u8; 32]) -> [u8; 32] {
+        hash::hashv(&[BRIDGE_DELIVERY_DOMAIN, msg_hash]).to_bytes()
+    }
+
+    // what guardians sign on the source chain once the message with `msg_hash` can no
+    // longer be delivered
+    pub fn refund_hash(msg_hash: &[u8; 32]) -> [u8; 32] {
+        hash::hashv(&[BRIDGE_REFUND_DOMAIN, msg_hash]).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::states::NftCreator;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn token_message(deadline: u64) -> BridgeMessage {
+        BridgeMessage {
+            source_chain_id: 1,
+            source_emitter: [0x11; 32],
+            destination_chain_id: 3,
+            destination_emitter: [0x22; 32],
+            sender: [0x33; 32],
+            recipient: [0x44; 32],
+            sequence: 7,
+            deadline,
+            payload: BridgePayload::Token {
+                source_mint: Pubkey::new_from_array([0x55; 32]),
+                target_mint: Pubkey::new_from_array([0x66; 32]),
+                amount: 1_000_000,
+                additional_sol_gas: 5_000,
+            },
+        }
+    }
+
+    fn expected_token_header(deadline: u64) -> Vec<u8> {
+        let parts: &[&[u8]] = &[
+            b"bridge_message".as_ref(),
+            &[2],
+            crate::ID.as_ref(),
+            &[0x00, 0x01],
+            &[0x11; 32],
+            &[0x00, 0x03],
+            &[0x22; 32],
+            &[0x33; 32],
+            &[0x44; 32],
+            &[0, 0, 0, 0, 0, 0, 0, 7],
+            &deadline.to_be_bytes(),
+        ];
+        parts.concat()
+    }
+
+    #[test]
+    fn encodes_token_message_without_deadline() {
+        let header = expected_token_header(0);
+        let parts: &[&[u8]] = &[
+            &header,
+            &[0],
+            &[0x55; 32],
+            &[0x66; 32],
+            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x0f, 0x42, 0x40],
+            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x13, 0x88],
+        ];
+        let expected = parts.concat();
+        let message = token_message(0);
+        assert_eq!(message.encode().unwrap(), expected);
+        assert_eq!(
+            hex(&message.hash(ChainKind::Svm).unwrap()),
+            "542ad26fa62e6d4ad99573997ae2f676623768b412ad1bbdb2c445de44503b53"
+        );
+        assert_eq!(
+            hex(&message.hash(ChainKind::Evm).unwrap()),
+            "6b8358c295a63317ed661cfbde077b9e62d605aa987f33d4edaed3c2269607e1"
+        );
+    }
+
+    #[test]
+    fn encodes_token_message_with_deadline() {
+        let deadline = 1_700_000_000;
+        let message = token_message(deadline);
+        let encoded = message.encode().unwrap();
+        // the deadline closes the header, right before the payload kind
+        let deadline_offset = expected_token_header(deadline).len() - 8;
+        assert_eq!(
+            &encoded[..expected_token_header(deadline).len()],
+            expected_token_header(deadline).as_slice()
+        );
+        assert_eq!(
+            &encoded[deadline_offset..deadline_offset + 8],
+            &[0x00, 0x00, 0x00, 0x00, 0x65, 0x53, 0xf1, 0x00]
+        );
+        assert_ne!(
+            message.hash(ChainKind::Svm).unwrap(),
+            token_message(0).hash(ChainKind::Svm).unwrap()
+        );
+        assert_eq!(
+            hex(&message.hash(ChainKind::Svm).unwrap()),
+            "6c4e6571c5b6af7c4a825310d0bc999e3d8657aca13f77329614d4779d3af219"
+        );
+        assert_eq!(
+            hex(&message.hash(ChainKind::Evm).unwrap()),
+            "7b6c87e60b3333a517bf8a297ca6de0bc1f8aa61daad9956c7c312f911f41745"
+        );
+    }
+
+    #[test]
+    fn encodes_sol_and_nft_payloads() {
+        let mut message = token_message(0);
+        message.payload = BridgePayload::Sol {
+            amount: 2,
+            deliver_as_wsol: true,
+        };
+        let encoded = message.encode().unwrap();
+        let header_len = expected_token_header(0).len();
+        assert_eq!(&encoded[header_len..], &[1, 0, 0, 0, 0, 0, 0, 0, 2, 1]);
+
+        message.payload = BridgePayload::Nft {
+            native_mint: Pubkey::new_from_array([0x77; 32]),
+            metadata: NftMetadata {
+                name: "A".to_string(),
+                symbol: "".to_string(),
+                uri: "u".to_string(),
+                creators: vec![NftCreator {
+                    address: Pubkey::new_from_array([0x88; 32]),
+                    share: 100,
+                }],
+                collection: None,
+            },
+        };
+        let parts: &[&[u8]] = &[
+            &[2],
+            &[0x77; 32],
+            &[0, 0, 0, 1, b'A'],
+            &[0, 0, 0, 0],
+            &[0, 0, 0, 1, b'u'],
+            &[1],
+            &[0x88; 32],
+            &[100],
+            &[0],
+        ];
+        assert_eq!(&message.encode().unwrap()[header_len..], parts.concat());
+    }
+
+    #[test]
+    fn evm_messages_hash_with_keccak256() {
+        assert_eq!(
+            hex(&ChainKind::Evm.hash_message(&[])),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(
+            hex(&ChainKind::Svm.hash_message(&[])),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn expires_only_with_a_deadline() {
+        assert!(!BridgeMessage::is_expired(0, u64::MAX));
+        assert!(!BridgeMessage::is_expired(100, 100));
+        assert!(BridgeMessage::is_expired(100, 101));
+    }
+
+    #[test]
+    fn refunds_only_after_the_deadline_and_clock_skew() {
+        assert_eq!(
+            BridgeMessage::check_refundable(0, u64::MAX).unwrap_err(),
+            BridgeHandlerError::RefundWithoutDeadline.into()
+        );
+        let deadline = 1_700_000_000;
+        assert_eq!(
+            BridgeMessage::check_refundable(deadline, deadline + 1).unwrap_err(),
+            BridgeHandlerError::RefundDeadlineNotReached.into()
+        );
+        assert_eq!(
+            BridgeMessage::check_refundable(deadline, deadline + REFUND_CLOCK_SKEW_SECONDS)
+                .unwrap_err(),
+            BridgeHandlerError::RefundDeadlineNotReached.into()
+        );
+        BridgeMessage::check_refundable(deadline, deadline + REFUND_CLOCK_SKEW_SECONDS + 1)
+            .unwrap();
+    }
+}
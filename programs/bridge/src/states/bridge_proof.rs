@@ -8,13 +8,22 @@ pub struct BridgeProof {
     pub tx_id: [u8; 64],
     pub user_account: Pubkey,
     pub created_at: u64,
+    pub payer: Pubkey, // gets the rent back when the receipt is closed
 }
 
+impl BridgeProof {
+    // receipts seeded with the hash of the source transaction id, written before the
+    // payer was recorded
+    pub const LEGACY_LEN: usize = 8 + 1 + 32 + 64 + 32 + 8;
+}
+
+// Seeded with `bridge_proof_v2` and the sender's sequence, so sequenced proofs never land
+// on a proof created under the caller-chosen nonces of the old seeds.
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct BridgeProofSourceChain {
     pub bump: u8,
-    pub msg_hash: [u8; 32], // Note that this msg_hash is not the same as the msg_hash in BridgeProof
+    pub msg_hash: [u8; 32], // hash of the BridgeMessage, delivered under the same hash on the target chain
     pub user_account: Pubkey,
     pub created_at: u64,
 }
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use solana_program::{hash, keccak};
+
+use crate::constants::SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS;
+use crate::errors::BridgeHandlerError;
+use crate::states::FixedFeeInfo;
+
+// Addresses on remote chains are carried as 32 bytes, EVM addresses are left-padded
+// with 12 zero bytes. Messages to and from EVM chains are hashed with keccak256 so the
+// remote contract can verify them. The encoding is pinned by the vectors in the
+// bridge_message tests.
+#[derive(InitSpace, Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+pub enum ChainKind {
+    Svm,
+    Evm,
+}
+
+impl ChainKind {
+    pub fn hash_message(&self, message_data: &[u8]) -> [u8; 32] {
+        match self {
+            ChainKind::Svm => hash::hash(message_data).to_bytes(),
+            ChainKind::Evm => keccak::hash(message_data).to_bytes(),
+        }
+    }
+
+    pub fn check_universal_address(&self, address: &[u8; 32]) -> Result<()> {
+        if *self == ChainKind::Evm {
+            require!(
+                address[..12].iter().all(|byte| *byte == 0),
+                BridgeHandlerError::InvalidUniversalAddress
+            );
+        }
+        Ok(())
+    }
+}
+
+// Per remote chain settings, bridge messages name the source and destination chain ids
+// and are routed through the config of the remote side.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ChainConfig {
+    pub bump: u8,
+    pub chain_id: u16,
+    pub chain_kind: ChainKind,
+    pub pause: bool,
+    pub emitter: [u8; 32],     // bridge handler address on the remote chain
+    pub guardian_info: Pubkey, // guardian set attesting messages from the remote chain
+    pub fee_info: FixedFeeInfo,
+    pub instant_bridge_cap_per_epoch: u64, // dollar value delivered instantly per epoch
+    pub instant_bridge_cap_remained_dollar: u64,
+    pub instant_bridge_cap_epoch: u64,
+    pub created_at: u64,
+}
+
+impl ChainConfig {
+    // the cap is refilled by the first delivery of each epoch
+    pub fn instant_cap_remaining(&self, epoch: u64) -> u64 {
+        if self.instant_bridge_cap_epoch != epoch {
+            self.instant_bridge_cap_per_epoch
+        } else {
+            self.instant_bridge_cap_remained_dollar
+        }
+    }
+
+    // seconds a delivery worth `dollar_value` still has to wait once its guardian signatures
+    // are `signatures_age` old, deliveries above the remaining cap wait for the full period
+    pub fn instant_cap_delay(&self, dollar_value: u64, epoch: u64, signatures_age: u64) -> u64 {
+        if self.instant_cap_remaining(epoch) >= dollar_value {
+            0
+        } else {
+            SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS.saturating_sub(signatures_age)
+        }
+    }
+
+    // deliveries within the cap consume it, delayed deliveries don't
+    pub fn consume_instant_cap(
+        &mut self,
+        dollar_value: u64,
+        epoch: u64,
+        signatures_age: u64,
+    ) -> Result<()> {
+        require!(
+            self.instant_cap_delay(dollar_value, epoch, signatures_age) == 0,
+            BridgeHandlerError::InstantBridgeCapExceeded
+        );
+
+        let remaining = self.instant_cap_remaining(epoch);
+        self.instant_bridge_cap_epoch = epoch;
+        self.instant_bridge_cap_remained_dollar = if remaining >= dollar_value {
+            remaining - dollar_value
+        } else {
+            remaining
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(per_epoch: u64, remained: u64, epoch: u64) -> ChainConfig {
+        ChainConfig {
+            bump: 0,
+            chain_id: 2,
+            chain_kind: ChainKind::Svm,
+            pause: false,
+            emitter: [0; 32],
+            guardian_info: Pubkey::default(),
+            fee_info: FixedFeeInfo {
+                bridge_asset_fee: 0,
+                bridge_message_fee: 0,
+                cross_chain_call_fee: 0,
+            },
+            instant_bridge_cap_per_epoch: per_epoch,
+            instant_bridge_cap_remained_dollar: remained,
+            instant_bridge_cap_epoch: epoch,
+            created_at: 0,
+        }
+    }
+
+    // quote_delivery reports a delay exactly when the delivery would be rejected right away
+    fn assert_quote_matches_delivery(config: &ChainConfig, dollar_value: u64, epoch: u64) {
+        let delay = config.instant_cap_delay(dollar_value, epoch, 0);
+        let mut delivered = config.clone();
+        let delivery = delivered.consume_instant_cap(dollar_value, epoch, 0);
+        assert_eq!(delay == 0, delivery.is_ok());
+
+        let mut delayed = config.clone();
+        delayed
+            .consume_instant_cap(dollar_value, epoch, delay)
+            .unwrap();
+        if delay > 0 {
+            let mut early = config.clone();
+            assert_eq!(
+                early
+                    .consume_instant_cap(dollar_value, epoch, delay - 1)
+                    .unwrap_err(),
+                BridgeHandlerError::InstantBridgeCapExceeded.into()
+            );
+        }
+    }
+
+    #[test]
+    fn deliveries_within_the_cap_consume_it() {
+        let mut config = config(1_000, 100, 5);
+        assert_quote_matches_delivery(&config, 100, 5);
+
+        assert_eq!(config.instant_cap_delay(100, 5, 0), 0);
+        config.consume_instant_cap(100, 5, 0).unwrap();
+        assert_eq!(config.instant_bridge_cap_remained_dollar, 0);
+        assert_eq!(config.instant_cap_remaining(5), 0);
+    }
+
+    #[test]
+    fn deliveries_over_the_cap_wait_without_consuming_it() {
+        let mut config = config(1_000, 100, 5);
+        assert_quote_matches_delivery(&config, 101, 5);
+
+        assert_eq!(
+            config.instant_cap_delay(101, 5, 0),
+            SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS
+        );
+        assert_eq!(
+            config.instant_cap_delay(101, 5, 60),
+            SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS - 60
+        );
+        config
+            .consume_instant_cap(101, 5, SOLANA_POST_INSTANT_CAP_AWAITING_TIME_SECONDS)
+            .unwrap();
+        assert_eq!(config.instant_bridge_cap_remained_dollar, 100);
+    }
+
+    #[test]
+    fn the_cap_is_refilled_in_a_new_epoch() {
+        let mut config = config(1_000, 0, 4);
+        assert_quote_matches_delivery(&config, 1_000, 5);
+        assert_quote_matches_delivery(&config, 1_001, 5);
+        assert_quote_matches_delivery(&config, 1, 4);
+
+        config.consume_instant_cap(400, 5, 0).unwrap();
+        assert_eq!(config.instant_bridge_cap_epoch, 5);
+        assert_eq!(config.instant_bridge_cap_remained_dollar, 600);
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::DELIVERY_BITMAP_PAGE_BYTES;
+use crate::errors::BridgeHandlerError;
+use crate::states::BridgeProof;
+
+// Replay protection for deliveries, one bit per sequence of a (source chain, emitter,
+// sender), paged so a delivery flips a bit instead of allocating a BridgeProof.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct DeliveryBitmap {
+    pub bump: u8,
+    pub page: u64,
+    pub bits: [u8; DELIVERY_BITMAP_PAGE_BYTES],
+}
+
+impl DeliveryBitmap {
+    pub const SEQUENCES_PER_PAGE: u64 = DELIVERY_BITMAP_PAGE_BYTES as u64 * 8;
+
+    pub fn page(sequence: u64) -> u64 {
+        sequence / Self::SEQUENCES_PER_PAGE
+    }
+
+    pub fn is_delivered(&self, sequence: u64) -> bool {
+        let (byte, mask) = Self::position(sequence);
+        self.bits[byte] & mask != 0
+    }
+
+    pub fn mark_delivered(&mut self, bump: u8, sequence: u64) -> Result<()> {
+        require!(
+            !self.is_delivered(sequence),
+            BridgeHandlerError::AlreadyDelivered
+        );
+
+        self.bump = bump;
+        self.page = Self::page(sequence);
+        let (byte, mask) = Self::position(sequence);
+        self.bits[byte] |= mask;
+        Ok(())
+    }
+
+    // marks the delivery and writes the optional BridgeProof receipt of the recipient
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_delivery(
+        &mut self,
+        bump: u8,
+        sequence: u64,
+        receipt: Option<(&mut BridgeProof, u8)>,
+        msg_hash: [u8; 32],
+        tx_id: [u8; 64],
+        user_account: Pubkey,
+        payer: Pubkey,
+    ) -> Result<()> {
+        self.mark_delivered(bump, sequence)?;
+        if let Some((bridge_proof, bridge_proof_bump)) = receipt {
+            bridge_proof.bump = bridge_proof_bump;
+            bridge_proof.msg_hash = msg_hash;
+            bridge_proof.tx_id = tx_id;
+            bridge_proof.user_account = user_account;
+            bridge_proof.created_at = Clock::get()?.unix_timestamp as u64;
+            bridge_proof.payer = payer;
+        }
+        Ok(())
+    }
+
+    fn position(sequence: u64) -> (usize, u8) {
+        let offset = sequence % Self::SEQUENCES_PER_PAGE;
+        ((offset / 8) as usize, 1 << (offset % 8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_page() -> DeliveryBitmap {
+        DeliveryBitmap {
+            bump: 0,
+            page: 0,
+            bits: [0; DELIVERY_BITMAP_PAGE_BYTES],
+        }
+    }
+
+    #[test]
+    fn pages_split_at_256_sequences() {
+        assert_eq!(DeliveryBitmap::SEQUENCES_PER_PAGE, 256);
+        assert_eq!(DeliveryBitmap::page(0), 0);
+        assert_eq!(DeliveryBitmap::page(255), 0);
+        assert_eq!(DeliveryBitmap::page(256), 1);
+        assert_eq!(DeliveryBitmap::page(511), 1);
+        assert_eq!(DeliveryBitmap::page(512), 2);
+        assert_eq!(DeliveryBitmap::page(u64::MAX), u64::MAX / 256);
+    }
+
+    #[test]
+    fn marks_the_last_and_first_bit_of_a_page() {
+        let mut first_page = empty_page();
+        first_page.mark_delivered(1, 255).unwrap();
+        assert!(first_page.is_delivered(255));
+        assert!(!first_page.is_delivered(254));
+        assert_eq!(first_page.page, 0);
+        assert_eq!(first_page.bits[31], 0b1000_0000);
+
+        let mut second_page = empty_page();
+        second_page.mark_delivered(1, 256).unwrap();
+        assert!(second_page.is_delivered(256));
+        assert!(!second_page.is_delivered(257));
+        assert_eq!(second_page.page, 1);
+        assert_eq!(second_page.bits[0], 0b0000_0001);
+        assert!(second_page.bits[1..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn rejects_a_second_delivery() {
+        let mut page = empty_page();
+        page.mark_delivered(1, 42).unwrap();
+        assert_eq!(
+            page.mark_delivered(1, 42).unwrap_err(),
+            BridgeHandlerError::AlreadyDelivered.into()
+        );
+        page.mark_delivered(1, 43).unwrap();
+        assert!(page.is_delivered(42) && page.is_delivered(43));
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_FEE_DISCOUNT_BPS;
+use crate::errors::BridgeHandlerError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FeeExemption {
+    pub bump: u8,
+    pub exempt_account: Pubkey, // either a signer wallet or the program calling into the bridge
+    pub discount_bps: u16,      // MAX_FEE_DISCOUNT_BPS means fully exempted
+    pub created_at: u64,
+}
+
+impl FeeExemption {
+    pub fn apply_discount(&self, fee: u64) -> Result<u64> {
+        let discount = (fee as u128)
+            .checked_mul(self.discount_bps as u128)
+            .and_then(|v| v.checked_div(MAX_FEE_DISCOUNT_BPS as u128))
+            .ok_or(BridgeHandlerError::FeeCalculationError)? as u64;
+
+        fee.checked_sub(discount)
+            .ok_or(BridgeHandlerError::FeeCalculationError.into())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BridgeHandlerError;
+
+// The vault of a pool mode token also holds locked user deposits, so the pool only
+// accounts for the liquidity providers added. Shares are priced from the pool totals and
+// withdrawals never reach past the pool's own liquidity.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct LiquidityPool {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub total_liquidity: u64,
+    pub total_shares: u64,
+    pub created_at: u64,
+}
+
+impl LiquidityPool {
+    // shares for `amount` of liquidity added, 1:1 while the pool is empty
+    pub fn shares_for_deposit(&self, amount: u64) -> Result<u64> {
+        if self.total_shares == 0 || self.total_liquidity == 0 {
+            return Ok(amount);
+        }
+        Self::mul_div(amount, self.total_shares, self.total_liquidity)
+    }
+
+    // liquidity paid out for burning `shares`
+    pub fn amount_for_shares(&self, shares: u64) -> Result<u64> {
+        require!(
+            shares <= self.total_shares,
+            BridgeHandlerError::InsufficientFunds
+        );
+        Self::mul_div(shares, self.total_liquidity, self.total_shares)
+    }
+
+    pub fn add_liquidity(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.total_liquidity = self
+            .total_liquidity
+            .checked_add(amount)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        self.total_shares = self
+            .total_shares
+            .checked_add(shares)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        Ok(())
+    }
+
+    pub fn remove_liquidity(&mut self, amount: u64, shares: u64) -> Result<()> {
+        self.total_liquidity = self
+            .total_liquidity
+            .checked_sub(amount)
+            .ok_or(BridgeHandlerError::InsufficientFunds)?;
+        self.total_shares = self
+            .total_shares
+            .checked_sub(shares)
+            .ok_or(BridgeHandlerError::InsufficientFunds)?;
+        Ok(())
+    }
+
+    // rounds down, in favour of the pool
+    fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+        let result = (value as u128)
+            .checked_mul(numerator as u128)
+            .and_then(|product| product.checked_div(denominator as u128))
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        u64::try_from(result).map_err(|_| BridgeHandlerError::BridgeAmtCalculationError.into())
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+// Swaps a legacy wrapped mint 1:1 into its replacement, keyed by the legacy mint
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MintMigration {
+    pub bump: u8,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub migrated_supply: u64,
+    pub created_at: u64,
+}
@@ -12,3 +12,30 @@ pub use verified_signatures::*;
 
 pub mod token_info;
 pub use token_info::*;
+
+pub mod fee_exemption;
+pub use fee_exemption::*;
+
+pub mod token_attestation;
+pub use token_attestation::*;
+
+pub mod nft_info;
+pub use nft_info::*;
+
+pub mod liquidity_pool;
+pub use liquidity_pool::*;
+
+pub mod mint_migration;
+pub use mint_migration::*;
+
+pub mod chain_config;
+pub use chain_config::*;
+
+pub mod bridge_message;
+pub use bridge_message::*;
+
+pub mod sender_sequence;
+pub use sender_sequence::*;
+
+pub mod delivery_bitmap;
+pub use delivery_bitmap::*;
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::accounts::Metadata as MetaplexMetadata;
+use anchor_spl::metadata::mpl_token_metadata::MAX_CREATOR_LIMIT;
+
+use crate::errors::BridgeHandlerError;
+
+// Lives on the chain where the NFT is wrapped, keyed by the native mint
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct NftInfo {
+    pub bump: u8,
+    pub native_mint: Pubkey,
+    pub native_chain_id: u16, // wrapped NFTs are only sent back to their native chain
+    pub wrapped_mint: Pubkey, // default while the NFT is back on its native chain
+    pub generation: u64,      // burnt wrapped mints can't be reused, so each wrap gets a new mint
+    pub native_collection: Option<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct NftCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+// Metadata of the native NFT carried in the bridge message. Creators can't be verified
+// on the other chain and only a verified collection is carried.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NftMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creators: Vec<NftCreator>,
+    pub collection: Option<Pubkey>,
+}
+
+impl NftMetadata {
+    pub fn from_metaplex(metadata: &AccountInfo, mint: &Pubkey) -> Result<Self> {
+        require_keys_eq!(
+            metadata.key(),
+            MetaplexMetadata::find_pda(mint).0,
+            BridgeHandlerError::InvalidTokenMetadata
+        );
+
+        let metadata_data = metadata.try_borrow_data()?;
+        let metaplex_metadata = MetaplexMetadata::safe_deserialize(&metadata_data)
+            .map_err(|_| BridgeHandlerError::InvalidTokenMetadata)?;
+
+        // Metaplex pads the fields with null bytes up to their max length
+        Ok(Self {
+            name: metaplex_metadata.name.trim_end_matches('\0').to_string(),
+            symbol: metaplex_metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: metaplex_metadata.uri.trim_end_matches('\0').to_string(),
+            creators: metaplex_metadata
+                .creators
+                .unwrap_or_default()
+                .iter()
+                .map(|creator| NftCreator {
+                    address: creator.address,
+                    share: creator.share,
+                })
+                .collect(),
+            collection: metaplex_metadata
+                .collection
+                .filter(|collection| collection.verified)
+                .map(|collection| collection.key),
+        })
+    }
+
+    // appended to the payload of a BridgeMessage
+    pub fn encode(&self, message_data: &mut Vec<u8>) -> Result<()> {
+        require!(
+            self.creators.len() <= MAX_CREATOR_LIMIT,
+            BridgeHandlerError::InvalidTokenMetadata
+        );
+
+        for field in [&self.name, &self.symbol, &self.uri] {
+            message_data.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            message_data.extend_from_slice(field.as_bytes());
+        }
+        message_data.push(self.creators.len() as u8);
+        for creator in &self.creators {
+            message_data.extend_from_slice(&creator.address.to_bytes());
+            message_data.push(creator.share);
+        }
+        match self.collection {
+            Some(collection) => {
+                message_data.push(1);
+                message_data.extend_from_slice(&collection.to_bytes());
+            }
+            None => message_data.push(0),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BridgeHandlerError;
+
+// Allocates the sequences of a sender's deposits on-chain, so deposits of different
+// senders don't contend on a shared counter.
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SenderSequence {
+    pub bump: u8,
+    pub sender: Pubkey,
+    pub next_sequence: u64,
+}
+
+impl SenderSequence {
+    // returns the sequence of the current deposit, initializing the account on first use
+    pub fn allocate(&mut self, bump: u8, sender: Pubkey) -> Result<u64> {
+        if self.sender == Pubkey::default() {
+            self.bump = bump;
+            self.sender = sender;
+        }
+
+        let sequence = self.next_sequence;
+        self.next_sequence = sequence
+            .checked_add(1)
+            .ok_or(BridgeHandlerError::SequenceOverflow)?;
+        Ok(sequence)
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::{
+    MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
+use solana_program::hash::hash;
+
+use crate::states::TOKEN_ATTESTATION_DOMAIN;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct TokenAttestation {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    #[max_len(MAX_NAME_LENGTH)]
+    pub name: String,
+    #[max_len(MAX_SYMBOL_LENGTH)]
+    pub symbol: String,
+    #[max_len(MAX_URI_LENGTH)]
+    pub uri: String,
+    pub msg_hash: [u8; 32], // signed by guardians and submitted to register_token on the target chain
+    pub created_at: u64,
+}
+
+impl TokenAttestation {
+    // bound to the program and to the bridge handler of the native chain, like BridgeMessage
+    pub fn message_hash(
+        source_chain_id: u16,
+        source_emitter: &[u8; 32],
+        mint: &Pubkey,
+        decimals: u8,
+        name: &str,
+        symbol: &str,
+        uri: &str,
+    ) -> [u8; 32] {
+        let mut message_data = Vec::new();
+        message_data.extend_from_slice(TOKEN_ATTESTATION_DOMAIN);
+        message_data.extend_from_slice(&crate::ID.to_bytes());
+        message_data.extend_from_slice(&source_chain_id.to_be_bytes());
+        message_data.extend_from_slice(source_emitter);
+        message_data.extend_from_slice(&mint.to_bytes());
+        message_data.push(decimals);
+        for field in [name, symbol, uri] {
+            message_data.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            message_data.extend_from_slice(field.as_bytes());
+        }
+        hash(message_data.as_ref()).to_bytes()
+    }
+}
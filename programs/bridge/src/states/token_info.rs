@@ -1,5 +1,29 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{DELIST_GRACE_PERIOD_SECONDS, MAX_MAPPING_MIGRATIONS};
+use crate::errors::BridgeHandlerError;
+use crate::states::Chain;
+
+// Native tokens are proposed by the token admin or attested, and only bridgeable once
+// activated. Wrapped tokens are active as soon as they are created.
+#[derive(InitSpace, Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+pub enum TokenStatus {
+    Proposed,
+    Active,
+    Paused,
+    Delisted,
+}
+
+#[derive(InitSpace, Clone, Copy, Debug, PartialEq, AnchorSerialize, AnchorDeserialize)]
+pub struct MappingMigration {
+    pub old_counterpart_mint: Pubkey,
+    pub old_counterpart_decimals: u8,
+    pub new_counterpart_mint: Pubkey,
+    pub new_counterpart_decimals: u8,
+    pub migrated_by: Pubkey,
+    pub migrated_at: u64,
+}
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct TokenInfo {
@@ -8,5 +32,215 @@ pub struct TokenInfo {
     pub solayer_mint: Pubkey,
     pub is_solana_native_token: bool,
     pub is_solayer_native_token: bool,
-    pub pause: bool,
+    pub status: TokenStatus,
+    pub metadata_nonce: u64, // nonce of the last guardian-attested metadata sync
+    pub solana_decimals: u8,
+    pub solayer_decimals: u8,
+    pub delisted_at: u64, // deliveries are accepted until the grace period after this ends
+    pub migrated_supply: u64, // swapped out of a legacy wrapped mint, or into its replacement
+    pub min_transfer_amount: u64, // in message precision, also the dust threshold
+    pub max_transfer_amount: u64, // in message precision, 0 means uncapped
+    #[max_len(MAX_MAPPING_MIGRATIONS)]
+    pub migrations: Vec<MappingMigration>,
+}
+
+impl TokenInfo {
+    // discriminator, bump, both mints, both native flags and the old pause flag
+    pub const LEGACY_LEN: usize = 8 + 1 + 32 + 32 + 1 + 1 + 1;
+
+    // every mapping starts without metadata syncs, limits or migrations
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        bump: u8,
+        solana_mint: Pubkey,
+        solayer_mint: Pubkey,
+        is_solana_native_token: bool,
+        is_solayer_native_token: bool,
+        solana_decimals: u8,
+        solayer_decimals: u8,
+        status: TokenStatus,
+    ) {
+        self.bump = bump;
+        self.solana_mint = solana_mint;
+        self.solayer_mint = solayer_mint;
+        self.is_solana_native_token = is_solana_native_token;
+        self.is_solayer_native_token = is_solayer_native_token;
+        self.solana_decimals = solana_decimals;
+        self.solayer_decimals = solayer_decimals;
+        self.status = status;
+        self.metadata_nonce = 0;
+        self.delisted_at = 0;
+        self.migrated_supply = 0;
+        self.min_transfer_amount = 0;
+        self.max_transfer_amount = 0;
+        self.migrations = Vec::new();
+    }
+
+    pub fn check_bridgeable(&self) -> Result<()> {
+        match self.status {
+            TokenStatus::Active => Ok(()),
+            TokenStatus::Paused => Err(BridgeHandlerError::TokenPaused.into()),
+            TokenStatus::Proposed => Err(BridgeHandlerError::TokenNotExists.into()),
+            TokenStatus::Delisted => Err(BridgeHandlerError::TokenDelisted.into()),
+        }
+    }
+
+    // in-flight transfers of a delisted token can still be delivered during the grace period,
+    // paused tokens hold deliveries until they are resumed
+    pub fn check_deliverable(&self, now: u64) -> Result<()> {
+        match self.status {
+            TokenStatus::Active => Ok(()),
+            TokenStatus::Paused => Err(BridgeHandlerError::TokenPaused.into()),
+            TokenStatus::Proposed => Err(BridgeHandlerError::TokenNotExists.into()),
+            TokenStatus::Delisted => {
+                let grace_period_end = self
+                    .delisted_at
+                    .checked_add(DELIST_GRACE_PERIOD_SECONDS)
+                    .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+                require!(now < grace_period_end, BridgeHandlerError::TokenDelisted);
+                Ok(())
+            }
+        }
+    }
+
+    // checked when a transfer is sent and again when it is delivered, so transfers in flight
+    // when the limits are tightened wait for them to be restored or are refunded after their
+    // deadline. limits are in message precision.
+    pub fn check_transfer_amount(&self, message_amount: u64) -> Result<()> {
+        require!(
+            message_amount >= self.min_transfer_amount,
+            BridgeHandlerError::TransferAmountTooSmall
+        );
+        require!(
+            self.max_transfer_amount == 0 || message_amount <= self.max_transfer_amount,
+            BridgeHandlerError::TransferAmountTooLarge
+        );
+        Ok(())
+    }
+
+    // mappings pair a Solana mint with a Solayer mint, so tokens can only be bridged through
+    // the chain config of the other chain of the pair
+    pub fn check_counterpart_chain(chain: Chain, chain_id: u16) -> Result<()> {
+        require!(
+            chain_id == chain.counterpart().chain_id(),
+            BridgeHandlerError::InvalidCounterpartChain
+        );
+        Ok(())
+    }
+
+    // wrapped mints are created by register_token at the same PDA on the other chain, as both
+    // bridge handlers share the program id and init nonce
+    pub fn wrapped_mint(bridge_handler: &Pubkey, native_mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"mint", bridge_handler.as_ref(), native_mint.as_ref()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    // amounts have to scale between the two precisions, and a wrapped mint never has less
+    // precision than its native mint
+    pub fn check_counterpart_decimals(
+        decimals: u8,
+        counterpart_decimals: u8,
+        is_wrapped_counterpart: bool,
+    ) -> Result<()> {
+        require!(
+            !is_wrapped_counterpart || counterpart_decimals >= decimals,
+            BridgeHandlerError::InvalidTargetDecimals
+        );
+        Self::scaling_factor_for(
+            decimals.max(counterpart_decimals),
+            decimals.min(counterpart_decimals),
+        )
+        .map_err(|_| BridgeHandlerError::InvalidTargetDecimals)?;
+        Ok(())
+    }
+
+    // both chains hold vaults and deliver from them
+    pub fn is_pool_mode(&self) -> bool {
+        self.is_solana_native_token && self.is_solayer_native_token
+    }
+
+    // the mint on the other chain
+    pub fn counterpart_mint(&self, chain: Chain) -> Pubkey {
+        match chain {
+            Chain::Solana => self.solayer_mint,
+            Chain::Solayer => self.solana_mint,
+        }
+    }
+
+    pub fn decimals(&self, chain: Chain) -> u8 {
+        match chain {
+            Chain::Solana => self.solana_decimals,
+            Chain::Solayer => self.solayer_decimals,
+        }
+    }
+
+    // bridge messages carry amounts in the lower precision of the two mints
+    pub fn message_decimals(&self) -> u8 {
+        self.solana_decimals.min(self.solayer_decimals)
+    }
+
+    // splits an amount of the mint on `chain` into the message amount and the dust
+    // that can't be represented in the message precision
+    pub fn normalize_amount(&self, chain: Chain, amount: u64) -> Result<(u64, u64)> {
+        let factor = self.scaling_factor(chain)?;
+        Ok((amount / factor, amount % factor))
+    }
+
+    // converts a message amount back into an amount of the mint on `chain`
+    pub fn denormalize_amount(&self, chain: Chain, amount: u64) -> Result<u64> {
+        self.denormalize_amount_for(chain, &self.counterpart_mint(chain), amount)
+    }
+
+    // converts a message amount exchanged with `counterpart_mint` into an amount of the mint
+    // on `chain`, in the precision that was in force for that counterpart
+    pub fn denormalize_amount_for(
+        &self,
+        chain: Chain,
+        counterpart_mint: &Pubkey,
+        amount: u64,
+    ) -> Result<u64> {
+        let message_decimals = self
+            .decimals(chain)
+            .min(self.counterpart_decimals(chain, counterpart_mint)?);
+        amount
+            .checked_mul(Self::scaling_factor_for(
+                self.decimals(chain),
+                message_decimals,
+            )?)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError.into())
+    }
+
+    // decimals of `counterpart_mint` as the counterpart of the mint on `chain`, mints replaced
+    // by a mapping migration keep the decimals they had when they were migrated away
+    pub fn counterpart_decimals(&self, chain: Chain, counterpart_mint: &Pubkey) -> Result<u8> {
+        if *counterpart_mint == self.counterpart_mint(chain) {
+            return Ok(match chain {
+                Chain::Solana => self.solayer_decimals,
+                Chain::Solayer => self.solana_decimals,
+            });
+        }
+        self.migrations
+            .iter()
+            .rev()
+            .find(|migration| migration.old_counterpart_mint == *counterpart_mint)
+            .map(|migration| migration.old_counterpart_decimals)
+            .ok_or(BridgeHandlerError::InvalidCounterpartMint.into())
+    }
+
+    fn scaling_factor(&self, chain: Chain) -> Result<u64> {
+        Self::scaling_factor_for(self.decimals(chain), self.message_decimals())
+    }
+
+    fn scaling_factor_for(decimals: u8, message_decimals: u8) -> Result<u64> {
+        let exponent = decimals
+            .checked_sub(message_decimals)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError)?;
+        10u64
+            .checked_pow(exponent as u32)
+            .ok_or(BridgeHandlerError::BridgeAmtCalculationError.into())
+    }
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::MAX_GUARDIAN_SIGNATURES;
+use crate::errors::BridgeHandlerError;
 
 #[account]
 #[derive(InitSpace, Debug)]
@@ -9,4 +10,30 @@ pub struct VerifiedSignatures {
     #[max_len(MAX_GUARDIAN_SIGNATURES)]
     pub pubkey_index: Vec<u8>,
     pub created_at: u64,
+    pub payer: Pubkey, // funded the account, gets the rent back when it is closed
+}
+
+impl VerifiedSignatures {
+    // discriminator, bump, signer indexes and creation time
+    pub const LEGACY_LEN: usize = 8 + 1 + (4 + MAX_GUARDIAN_SIGNATURES) + 8;
+
+    pub fn check_guardian_threshold(
+        &self,
+        guardian_count: usize,
+        guardian_threshold: u8,
+    ) -> Result<()> {
+        require!(
+            !self.pubkey_index.is_empty()
+                && self.pubkey_index.len() <= guardian_count
+                && self.pubkey_index.len() <= MAX_GUARDIAN_SIGNATURES,
+            BridgeHandlerError::InvalidSignerCount
+        );
+
+        // verify sigs meet threshold
+        require!(
+            self.pubkey_index.len() >= guardian_threshold as usize,
+            BridgeHandlerError::GuardianThresholdNotMet
+        );
+        Ok(())
+    }
 }
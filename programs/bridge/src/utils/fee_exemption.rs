@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::BridgeHandlerError;
+use crate::states::FeeExemption;
+
+// Returns the program of the top-level instruction currently being executed, which is
+// the integrator program when the bridge is invoked through CPI. Only a direct CPI from
+// that program counts, deeper calls may come from any program it invokes.
+pub fn get_calling_program_id(ix_sysvar_account: &AccountInfo) -> Result<Option<Pubkey>> {
+    if get_stack_height() != TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+        return Ok(None);
+    }
+
+    let current_index = load_current_index_checked(ix_sysvar_account)?;
+    let current_ix = load_instruction_at_checked(current_index as usize, ix_sysvar_account)?;
+    Ok(Some(current_ix.program_id))
+}
+
+pub fn apply_fee_exemption(
+    fee: u64,
+    signer: &Pubkey,
+    fee_exemption: Option<&FeeExemption>,
+    ix_sysvar_account: Option<&AccountInfo>,
+) -> Result<u64> {
+    let fee_exemption = match fee_exemption {
+        Some(fee_exemption) => fee_exemption,
+        None => return Ok(fee),
+    };
+
+    let is_exempted = if fee_exemption.exempt_account == *signer {
+        true
+    } else if let Some(ix_sysvar_account) = ix_sysvar_account {
+        get_calling_program_id(ix_sysvar_account)? == Some(fee_exemption.exempt_account)
+    } else {
+        false
+    };
+    require!(is_exempted, BridgeHandlerError::InvalidFeeExemption);
+
+    msg!(
+        "applying {} bps fee discount for {:?}",
+        fee_exemption.discount_bps,
+        fee_exemption.exempt_account
+    );
+    fee_exemption.apply_discount(fee)
+}
@@ -1,8 +1,35 @@
 pub mod verify_ed25519;
-use anchor_lang::prelude::Pubkey;
+use anchor_lang::prelude::{Pubkey, Result, ToAccountMetas};
 pub use verify_ed25519::*;
 
+use crate::errors::BridgeHandlerError;
+
+pub mod fee_exemption;
+pub use fee_exemption::*;
+
+pub mod token_extensions;
+pub use token_extensions::*;
+
+pub mod token_metadata;
+pub use token_metadata::*;
+
 #[inline(always)]
 pub const fn pubkey_from_str(s: &str) -> Pubkey {
     Pubkey::new_from_array(five8_const::decode_32_const(s))
 }
+
+// position of `key` in the instruction accounts, as the native SOL precompiles expect it
+pub fn instruction_account_index<T: ToAccountMetas>(accounts: &T, key: &Pubkey) -> Result<u64> {
+    accounts
+        .to_account_metas(None)
+        .iter()
+        .position(|meta| meta.pubkey == *key)
+        .map(|index| index as u64)
+        .ok_or(BridgeHandlerError::AccountNotInInstruction.into())
+}
+
+pub mod realloc;
+pub use realloc::*;
+
+pub mod sol_vault;
+pub use sol_vault::*;
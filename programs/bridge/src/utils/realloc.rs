@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::errors::BridgeHandlerError;
+
+// Grows an account of this program that was created with an older, shorter layout. New
+// bytes are zeroed and the extra rent is paid by `payer`. Accounts already at `new_len`
+// are left untouched.
+pub fn realloc_program_account<'info, T: Discriminator>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    require_keys_eq!(
+        *account.owner,
+        crate::ID,
+        BridgeHandlerError::InvalidAccountLayout
+    );
+    {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == T::DISCRIMINATOR,
+            BridgeHandlerError::InvalidAccountLayout
+        );
+    }
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        let ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        transfer(ctx, rent_shortfall)?;
+    }
+
+    msg!("realloc {:?} to {:?} bytes", account.key(), new_len);
+    account.realloc(new_len, true)?;
+    Ok(())
+}
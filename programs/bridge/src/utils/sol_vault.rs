@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::errors::BridgeHandlerError;
+
+// SOL bridged out of Solana is held by a system owned PDA of the bridge handler, so that
+// deposits don't write-lock the handler. The vault keeps its rent exempt minimum.
+pub fn transfer_sol_from_vault<'info>(
+    system_program: &AccountInfo<'info>,
+    sol_vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    bridge_handler: &Pubkey,
+    sol_vault_bump: u8,
+    lamports: u64,
+) -> Result<()> {
+    require!(
+        Rent::get()?.minimum_balance(0) + lamports <= sol_vault.lamports(),
+        BridgeHandlerError::InsufficientFunds
+    );
+
+    let bump = [sol_vault_bump];
+    let signer_seeds: [&[&[u8]]; 1] = [&[b"sol_vault", bridge_handler.as_ref(), &bump][..]];
+    let ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Transfer {
+            from: sol_vault.clone(),
+            to: to.clone(),
+        },
+        &signer_seeds[..],
+    );
+
+    transfer(ctx, lamports)
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::onchain::invoke_transfer_checked;
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+
+use crate::errors::BridgeHandlerError;
+
+// Mint extensions that don't change how the bridge vault holds or moves tokens. Transfer
+// hooks are supported as every transfer forwards the extra accounts the hook needs.
+pub const SUPPORTED_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::TransferHook,
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+];
+
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    for extension_type in mint_with_extension.get_extension_types()? {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension_type) {
+            msg!("unsupported mint extension: {:?}", extension_type);
+            return Err(BridgeHandlerError::UnsupportedMintExtension.into());
+        }
+    }
+
+    Ok(())
+}
+
+// Fee withheld by the token program when transferring `amount` of a Token-2022 mint
+// with the TransferFee extension, zero for every other mint
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(0);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    match mint_with_extension.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => Ok(transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(BridgeHandlerError::FeeCalculationError)?),
+        Err(_) => Ok(0),
+    }
+}
+
+// transfer_checked that resolves the extra account metas of a TransferHook mint from
+// `remaining_accounts`, behaves like a plain transfer_checked for every other mint
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook<'info>(
+    token_program: &AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        remaining_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )
+    .map_err(Into::into)
+}
@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::metadata::mpl_token_metadata::accounts::Metadata as MetaplexMetadata;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{update_metadata_accounts_v2, UpdateMetadataAccountsV2};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint;
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_interface::{
+    token_metadata_initialize, token_metadata_update_field, TokenMetadataInitialize,
+    TokenMetadataUpdateField,
+};
+
+use crate::errors::BridgeHandlerError;
+
+// Token-2022 reallocs the mint when writing metadata but doesn't fund the extra rent
+pub fn top_up_rent_exemption<'info>(
+    payer: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    additional_space: usize,
+) -> Result<()> {
+    let new_len = account.data_len() + additional_space;
+    let lamports_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+
+    if lamports_needed > 0 {
+        let ctx = CpiContext::new(
+            system_program,
+            Transfer {
+                from: payer,
+                to: account,
+            },
+        );
+        transfer(ctx, lamports_needed)?;
+    }
+
+    Ok(())
+}
+
+// Writes the token-metadata extension of a Token-2022 mint whose metadata pointer
+// points to itself, with the bridge handler as both mint and update authority
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_token_2022_metadata<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    bridge_handler: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(bridge_handler.key()))?,
+        mint: mint.key(),
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    top_up_rent_exemption(
+        payer,
+        mint.clone(),
+        system_program,
+        token_metadata.tlv_size_of()?,
+    )?;
+
+    let ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        TokenMetadataInitialize {
+            token_program_id: token_program,
+            metadata: mint.clone(),
+            update_authority: bridge_handler.clone(),
+            mint_authority: bridge_handler,
+            mint,
+        },
+        signer_seeds,
+    );
+
+    token_metadata_initialize(ctx, name, symbol, uri)
+}
+
+pub fn has_token_2022_metadata(mint: &AccountInfo) -> Result<bool> {
+    if mint.owner != &anchor_spl::token_2022::ID {
+        return Ok(false);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint_with_extension
+        .get_variable_len_extension::<TokenMetadata>()
+        .is_ok())
+}
+
+// Reads name, symbol and uri of a mint from its Token-2022 token-metadata extension,
+// falling back to its Metaplex metadata account
+pub fn read_token_metadata(
+    mint: &AccountInfo,
+    metadata: &AccountInfo,
+) -> Result<(String, String, String)> {
+    if has_token_2022_metadata(mint)? {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let token_metadata = mint_with_extension.get_variable_len_extension::<TokenMetadata>()?;
+        return Ok((
+            token_metadata.name,
+            token_metadata.symbol,
+            token_metadata.uri,
+        ));
+    }
+
+    require_keys_eq!(
+        metadata.key(),
+        MetaplexMetadata::find_pda(&mint.key()).0,
+        BridgeHandlerError::InvalidTokenMetadata
+    );
+
+    let metadata_data = metadata.try_borrow_data()?;
+    let metaplex_metadata = MetaplexMetadata::safe_deserialize(&metadata_data)
+        .map_err(|_| BridgeHandlerError::InvalidTokenMetadata)?;
+
+    // Metaplex pads the fields with null bytes up to their max length
+    Ok((
+        metaplex_metadata.name.trim_end_matches('\0').to_string(),
+        metaplex_metadata.symbol.trim_end_matches('\0').to_string(),
+        metaplex_metadata.uri.trim_end_matches('\0').to_string(),
+    ))
+}
+
+// Accounts needed to rewrite the metadata of a wrapped mint, whether it lives in the
+// Token-2022 token-metadata extension or in a Metaplex metadata account
+pub struct WrappedTokenMetadataAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub bridge_handler: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+pub fn update_wrapped_token_metadata(
+    accounts: WrappedTokenMetadataAccounts,
+    signer_seeds: &[&[&[u8]]],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    if has_token_2022_metadata(&accounts.mint)? {
+        update_token_2022_metadata(accounts, signer_seeds, name, symbol, uri)
+    } else {
+        update_metaplex_metadata(accounts, signer_seeds, name, symbol, uri)
+    }
+}
+
+fn update_token_2022_metadata(
+    accounts: WrappedTokenMetadataAccounts,
+    signer_seeds: &[&[&[u8]]],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let (current_size, new_size) = {
+        let mint_data = accounts.mint.try_borrow_data()?;
+        let mint_with_extension = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let mut token_metadata =
+            mint_with_extension.get_variable_len_extension::<TokenMetadata>()?;
+        let current_size = token_metadata.tlv_size_of()?;
+        token_metadata.update(Field::Name, name.clone());
+        token_metadata.update(Field::Symbol, symbol.clone());
+        token_metadata.update(Field::Uri, uri.clone());
+        (current_size, token_metadata.tlv_size_of()?)
+    };
+
+    top_up_rent_exemption(
+        accounts.payer,
+        accounts.mint.clone(),
+        accounts.system_program,
+        new_size.saturating_sub(current_size),
+    )?;
+
+    for (field, value) in [
+        (Field::Name, name),
+        (Field::Symbol, symbol),
+        (Field::Uri, uri),
+    ] {
+        let ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            TokenMetadataUpdateField {
+                token_program_id: accounts.token_program.clone(),
+                metadata: accounts.mint.clone(),
+                update_authority: accounts.bridge_handler.clone(),
+            },
+            signer_seeds,
+        );
+        token_metadata_update_field(ctx, field, value)?;
+    }
+
+    Ok(())
+}
+
+fn update_metaplex_metadata(
+    accounts: WrappedTokenMetadataAccounts,
+    signer_seeds: &[&[&[u8]]],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    require_keys_eq!(
+        accounts.metadata.key(),
+        MetaplexMetadata::find_pda(&accounts.mint.key()).0,
+        BridgeHandlerError::InvalidTokenMetadata
+    );
+
+    let current_metadata = {
+        let metadata_data = accounts.metadata.try_borrow_data()?;
+        MetaplexMetadata::safe_deserialize(&metadata_data)
+            .map_err(|_| BridgeHandlerError::InvalidTokenMetadata)?
+    };
+
+    let token_metadata = DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: current_metadata.seller_fee_basis_points,
+        creators: current_metadata.creators,
+        collection: current_metadata.collection,
+        uses: current_metadata.uses,
+    };
+
+    let ctx = CpiContext::new_with_signer(
+        accounts.token_metadata_program,
+        UpdateMetadataAccountsV2 {
+            metadata: accounts.metadata,
+            update_authority: accounts.bridge_handler,
+        },
+        signer_seeds,
+    );
+
+    update_metadata_accounts_v2(ctx, None, Some(token_metadata), None, None)
+}